@@ -0,0 +1,200 @@
+use anyhow::{Context, Result};
+use aoc_common::Part;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::fs::{read_to_string, write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::days::Day;
+
+/// Median time spent in each phase of solving one input.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    #[serde(with = "micros")]
+    pub parse: Duration,
+    #[serde(with = "micros")]
+    pub part1: Duration,
+    #[serde(with = "micros")]
+    pub part2: Duration,
+}
+
+/// Previously saved timings, keyed by the day's crate directory.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Baseline(BTreeMap<String, Timings>);
+
+impl Timings {
+    fn phases(&self) -> [(&'static str, Duration); 3] {
+        [
+            ("parse", self.parse),
+            ("part1", self.part1),
+            ("part2", self.part2),
+        ]
+    }
+}
+
+impl Baseline {
+    /// Read a saved baseline, if there is one.
+    pub fn read(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = read_to_string(path)
+            .with_context(|| format!("Could not read baseline {}", path.display()))?;
+        let baseline = toml::from_str(&content)
+            .with_context(|| format!("Invalid baseline {}", path.display()))?;
+        Ok(Some(baseline))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self)?;
+        write(path, content).with_context(|| format!("Could not write baseline {}", path.display()))
+    }
+
+    pub fn get(&self, day: &Day) -> Option<&Timings> {
+        self.0.get(day.dir().to_str()?)
+    }
+
+    pub fn insert(&mut self, day: &Day, timings: Timings) {
+        self.0
+            .insert(day.dir().to_string_lossy().into_owned(), timings);
+    }
+}
+
+/// Solve `content` `runs` times and take the median of each phase.
+pub fn measure(day: &Day, content: &str, runs: usize) -> Result<Timings> {
+    let mut parse = Vec::with_capacity(runs);
+    let mut part1 = Vec::with_capacity(runs);
+    let mut part2 = Vec::with_capacity(runs);
+
+    for _ in 0..runs {
+        let report = (day.run)(content, &Part::ALL)?;
+        parse.push(report.parse);
+        for solved in report.parts {
+            match solved.part {
+                Part::One => part1.push(solved.elapsed),
+                Part::Two => part2.push(solved.elapsed),
+            }
+        }
+    }
+
+    Ok(Timings {
+        parse: median(parse),
+        part1: median(part1),
+        part2: median(part2),
+    })
+}
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+    durations
+        .get(durations.len() / 2)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Table of all measured phases, compared against the baseline where available.
+pub fn table(measured: &[(Day, Timings)], baseline: Option<&Baseline>) -> String {
+    let mut table = String::new();
+    // writing to a String cannot fail
+    let _ = write_table(&mut table, measured, baseline);
+    table
+}
+
+fn write_table(
+    f: &mut impl Write,
+    measured: &[(Day, Timings)],
+    baseline: Option<&Baseline>,
+) -> fmt::Result {
+    writeln!(
+        f,
+        "{:>3} {:<5} {:>12} {:>12} {:>8}",
+        "day", "phase", "time", "baseline", "change"
+    )?;
+    for (day, timings) in measured {
+        let saved = baseline.and_then(|b| b.get(day));
+        for (idx, (phase, time)) in timings.phases().into_iter().enumerate() {
+            let before = saved.map(|s| s.phases()[idx].1);
+            let (before, change) = match before {
+                Some(before) => (format!("{:.2?}", before), change(before, time)),
+                None => ("-".to_owned(), "-".to_owned()),
+            };
+            writeln!(
+                f,
+                "{:>3} {:<5} {:>12} {:>12} {:>8}",
+                format!("{:02}", day.day),
+                phase,
+                format!("{:.2?}", time),
+                before,
+                change
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn change(before: Duration, now: Duration) -> String {
+    if before.is_zero() {
+        return "-".to_owned();
+    }
+    let ratio = now.as_secs_f64() / before.as_secs_f64();
+    format!("{:+.1}%", (ratio - 1.0) * 100.0)
+}
+
+/// Store durations as (fractional) microseconds to keep the baseline file readable.
+mod micros {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_f64(d.as_nanos() as f64 / 1e3)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Duration, D::Error> {
+        let micros = f64::deserialize(d)?;
+        Duration::try_from_secs_f64(micros / 1e6).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_to_baseline() -> Result<()> {
+        let day = crate::days::get(1).unwrap();
+        let mut baseline = Baseline::default();
+        baseline.insert(
+            &day,
+            Timings {
+                parse: Duration::from_micros(100),
+                part1: Duration::from_micros(10),
+                part2: Duration::ZERO,
+            },
+        );
+        let baseline: Baseline = toml::from_str(&toml::to_string(&baseline)?)?;
+        assert_eq!(baseline.get(&day).unwrap().part1, Duration::from_micros(10));
+
+        let measured = Timings {
+            parse: Duration::from_micros(150),
+            part1: Duration::from_micros(5),
+            part2: Duration::from_micros(1),
+        };
+        let table = table(&[(day, measured)], Some(&baseline));
+        let rows: Vec<_> = table.lines().map(|l| l.split_whitespace().last()).collect();
+        assert_eq!(
+            rows,
+            vec![Some("change"), Some("+50.0%"), Some("-50.0%"), Some("-")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn median_of_runs() {
+        let runs = [3, 1, 2].into_iter().map(Duration::from_millis).collect();
+        assert_eq!(median(runs), Duration::from_millis(2));
+        assert_eq!(median(vec![]), Duration::ZERO);
+    }
+}
@@ -0,0 +1,69 @@
+use aoc_common::{cached_input, run, Runner, Solution};
+use std::path::PathBuf;
+
+/// Entry point for a single day of the advent calendar.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    pub run: Runner,
+}
+
+impl Day {
+    const fn of<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            run: run::<S>,
+        }
+    }
+
+    /// Directory of the day's crate, relative to the workspace root.
+    pub fn dir(&self) -> PathBuf {
+        PathBuf::from(format!("day_{:02}", self.day))
+    }
+
+    /// Puzzle input checked in next to the day's crate.
+    pub fn default_input(&self) -> PathBuf {
+        self.dir().join("input.txt")
+    }
+
+    /// Input to solve if none is given explicitly: the fetched input, otherwise the checked in one.
+    pub fn find_input(&self) -> Option<PathBuf> {
+        cached_input(self.day).or_else(|| {
+            let input = self.default_input();
+            input.is_file().then_some(input)
+        })
+    }
+}
+
+/// All solved days, in order.
+pub const DAYS: &[Day] = &[
+    Day::of::<day_01::Day01>(),
+    Day::of::<day_02::Day02>(),
+    Day::of::<day_03::Day03>(),
+    Day::of::<day_04::Day04>(),
+    Day::of::<day_05::Day05>(),
+    Day::of::<day_06::Day06>(),
+    Day::of::<day_07::Day07>(),
+    Day::of::<day_08::Day08>(),
+    Day::of::<day_09::Day09>(),
+    Day::of::<day_10::Day10>(),
+    Day::of::<day_11::Day11>(),
+    Day::of::<day_12::Day12>(),
+    Day::of::<day_13::Day13>(),
+    Day::of::<day_14::Day14>(),
+    Day::of::<day_15::Day15>(),
+    Day::of::<day_16::Day16>(),
+    Day::of::<day_17::Day17>(),
+    Day::of::<day_18::Day18>(),
+    Day::of::<day_19::Day19>(),
+    Day::of::<day_20::Day20>(),
+    Day::of::<day_21::Day21>(),
+    Day::of::<day_22::Day22>(),
+    Day::of::<day_23::Day23>(),
+    Day::of::<day_24::Day24>(),
+    Day::of::<day_25::Day25>(),
+];
+
+pub fn get(day: u8) -> Option<Day> {
+    DAYS.iter().find(|d| d.day == day).copied()
+}
@@ -0,0 +1,127 @@
+use anyhow::{bail, Context, Result};
+use aoc_common::cache_path;
+use std::fs::{create_dir_all, rename, write};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_ENDPOINT: &str = "https://adventofcode.com";
+const YEAR: u16 = 2021;
+
+/// Downloads puzzle inputs into the cache directory.
+pub struct Fetcher {
+    endpoint: String,
+    session: String,
+    cache: PathBuf,
+}
+
+impl Fetcher {
+    pub fn new(endpoint: &str, session: &str, cache: &Path) -> Self {
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+            cache: cache.to_owned(),
+        }
+    }
+
+    /// Fetch the input for `day` unless it is cached already (or `force` is set).
+    ///
+    /// Returns the path of the cached input and whether it was downloaded.
+    pub fn fetch(&self, day: u8, force: bool) -> Result<(PathBuf, bool)> {
+        let path = cache_path(&self.cache, day);
+        if path.is_file() && !force {
+            return Ok((path, false));
+        }
+
+        let url = format!("{}/{}/day/{}/input", self.endpoint, YEAR, day);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", "github.com/obreitwi/advent-of-code-2021-rust")
+            .call();
+        let content = match response {
+            Ok(response) => response
+                .into_string()
+                .with_context(|| format!("Could not read response from {}", url))?,
+            Err(ureq::Error::Status(code, response)) => {
+                let message = response.into_string().unwrap_or_default();
+                bail!("Fetching {} failed ({}): {}", url, code, message.trim());
+            }
+            Err(e) => return Err(e).with_context(|| format!("Could not fetch {}", url)),
+        };
+
+        create_dir_all(&self.cache)
+            .with_context(|| format!("Could not create cache {}", self.cache.display()))?;
+        // only complete downloads end up in the cache
+        let partial = path.with_extension("part");
+        write(&partial, content)
+            .with_context(|| format!("Could not write {}", partial.display()))?;
+        rename(&partial, &path)?;
+        Ok((path, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs::{read_to_string, remove_dir_all};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serve a single request with the given status and body, returning the request head.
+    fn serve_once(status: &str, body: &'static str) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let status = status.to_owned();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let head: Vec<String> = BufReader::new(stream.try_clone().unwrap())
+                .lines()
+                .map(|l| l.unwrap())
+                .take_while(|l| !l.is_empty())
+                .collect();
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            head
+        });
+        (endpoint, handle)
+    }
+
+    #[test]
+    fn fetch_into_cache() -> Result<()> {
+        let cache = env::temp_dir().join(format!("aoc-fetch-test-{}", std::process::id()));
+
+        let (endpoint, server) = serve_once("200 OK", "1\n2\n3\n");
+        let fetcher = Fetcher::new(&endpoint, "s3cr3t\n", &cache);
+        let (path, downloaded) = fetcher.fetch(7, false)?;
+        let head = server.join().unwrap();
+        assert!(downloaded);
+        assert_eq!(head[0], "GET /2021/day/7/input HTTP/1.1");
+        assert!(
+            head.iter().any(|l| l == "Cookie: session=s3cr3t"),
+            "{:?}",
+            head
+        );
+        assert_eq!(path, cache.join("day_07.txt"));
+        assert_eq!(read_to_string(&path)?, "1\n2\n3\n");
+
+        // cached inputs are not downloaded again (no server is listening)
+        assert_eq!(fetcher.fetch(7, false)?, (path, false));
+
+        let (endpoint, server) = serve_once("400 Bad Request", "Please log in.");
+        let err = Fetcher::new(&endpoint, "expired", &cache)
+            .fetch(7, true)
+            .unwrap_err();
+        server.join().unwrap();
+        assert!(err.to_string().contains("(400): Please log in."), "{}", err);
+        assert_eq!(read_to_string(cache.join("day_07.txt"))?, "1\n2\n3\n");
+
+        remove_dir_all(&cache)?;
+        Ok(())
+    }
+}
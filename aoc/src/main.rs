@@ -0,0 +1,264 @@
+use anyhow::{bail, Context, Result};
+use aoc_common::{cache_dir, Format, Part};
+use clap::{Parser, Subcommand};
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+mod bench;
+mod days;
+mod fetch;
+mod scaffold;
+mod verify;
+
+use bench::Baseline;
+use days::{Day, DAYS};
+use fetch::Fetcher;
+use verify::{Answers, Check};
+
+/// Runner for all Advent of Code 2021 solutions.
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day (or all days if none is given).
+    Run {
+        /// Day to solve.
+        day: Option<u8>,
+
+        /// Only solve the given part (1 or 2).
+        #[arg(short, long)]
+        part: Option<Part>,
+
+        /// Puzzle input (default: fetched input from the cache, then day_NN/input.txt).
+        #[arg(short, long, requires = "day")]
+        input: Option<PathBuf>,
+
+        /// Output format (text or json).
+        #[arg(long, default_value = "text")]
+        format: Format,
+    },
+
+    /// Check answers of all recorded inputs against known-good results.
+    Verify {
+        /// Only verify the given day.
+        day: Option<u8>,
+
+        /// Recorded answers.
+        #[arg(long, default_value = "answers.toml")]
+        answers: PathBuf,
+    },
+
+    /// Time parsing and both parts on each day's input (build with --release for useful numbers).
+    Bench {
+        /// Only benchmark the given day.
+        day: Option<u8>,
+
+        /// Number of runs per day, the median of which is reported.
+        #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+
+        /// Saved timings to compare against.
+        #[arg(long, default_value = "bench.toml")]
+        baseline: PathBuf,
+
+        /// Store the measured timings as new baseline.
+        #[arg(long)]
+        save: bool,
+    },
+
+    /// Download puzzle inputs into the cache directory.
+    Fetch {
+        /// Day to fetch (default: all solved days).
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// Session token (value of the `session` cookie on adventofcode.com).
+        #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+        session: String,
+
+        /// Server to fetch inputs from.
+        #[arg(long, env = "AOC_ENDPOINT", default_value = fetch::DEFAULT_ENDPOINT)]
+        endpoint: String,
+
+        /// Cache directory (default: $AOC_CACHE_DIR, else aoc-2021 in $XDG_CACHE_HOME or ~/.cache).
+        #[arg(long)]
+        cache: Option<PathBuf>,
+
+        /// Download again even if the input is cached already.
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Create the crate for a new day from the template and register it (run from workspace root).
+    New {
+        /// Day to create.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+        } => {
+            let parts = match part {
+                Some(part) => vec![part],
+                None => Part::ALL.to_vec(),
+            };
+            for selected in select(day)? {
+                let input = match input.clone().or_else(|| selected.find_input()) {
+                    Some(input) => input,
+                    None if day.is_none() => {
+                        eprintln!("day {:02}: no input, skipping", selected.day);
+                        continue;
+                    }
+                    None => bail!("No input for day {}, use fetch or --input.", selected.day),
+                };
+                solve(&selected, &input, &parts, format)?;
+            }
+        }
+        Command::Verify { day, answers } => {
+            verify_days(&select(day)?, &answers)?;
+        }
+        Command::Bench {
+            day,
+            runs,
+            baseline,
+            save,
+        } => {
+            bench_days(&select(day)?, runs as usize, &baseline, save)?;
+        }
+        Command::Fetch {
+            day,
+            session,
+            endpoint,
+            cache,
+            force,
+        } => {
+            let cache = match cache.or_else(cache_dir) {
+                Some(cache) => cache,
+                None => bail!("No cache directory, please specify one via --cache."),
+            };
+            let days = match day {
+                Some(day) => vec![day],
+                None => DAYS.iter().map(|d| d.day).collect(),
+            };
+            let fetcher = Fetcher::new(&endpoint, &session, &cache);
+            for day in days {
+                let (path, downloaded) = fetcher.fetch(day, force)?;
+                let status = if downloaded { "fetched" } else { "cached" };
+                println!("day {:02}: {} {}", day, status, path.display());
+            }
+        }
+        Command::New { day } => {
+            for path in scaffold::create(Path::new("."), day)? {
+                println!("wrote {}", path.display());
+            }
+            println!("Paste the example input into day_{:02}/debug.txt.", day);
+        }
+    }
+    Ok(())
+}
+
+fn select(day: Option<u8>) -> Result<Vec<Day>> {
+    match day {
+        Some(day) => match days::get(day) {
+            Some(day) => Ok(vec![day]),
+            None => bail!("Day {} is not solved (yet).", day),
+        },
+        None => Ok(DAYS.to_vec()),
+    }
+}
+
+fn solve(day: &Day, input: &Path, parts: &[Part], format: Format) -> Result<()> {
+    let content = read_to_string(input)
+        .with_context(|| format!("Could not read input {}", input.display()))?;
+    let report = (day.run)(&content, parts)?;
+    print!("{}", report.render(format, input)?);
+    Ok(())
+}
+
+fn verify_days(days: &[Day], answers: &Path) -> Result<()> {
+    let answers = Answers::read(answers)?;
+    let mut num_checked = 0;
+    let mut num_failed = 0;
+
+    for day in days {
+        for (input, expected) in answers.for_day(day) {
+            let checks = match verify::verify(day, input, expected) {
+                Ok(checks) => checks,
+                Err(e) => {
+                    num_failed += 1;
+                    println!("day {:02} {}: FAILED: {:#}", day.day, input, e);
+                    continue;
+                }
+            };
+            for check in checks {
+                num_checked += 1;
+                match check {
+                    Check::Match { part, .. } => {
+                        println!("day {:02} {} part {}: ok", day.day, input, part);
+                    }
+                    Check::Mismatch {
+                        part,
+                        expected,
+                        got,
+                    } => {
+                        num_failed += 1;
+                        println!("day {:02} {} part {}: MISMATCH", day.day, input, part);
+                        println!("  expected: {}", expected);
+                        println!("  got:      {}", got);
+                    }
+                }
+            }
+        }
+    }
+
+    println!("{} answers checked, {} failed", num_checked, num_failed);
+    if num_failed > 0 {
+        bail!("{} answers did not match the recorded results", num_failed);
+    }
+    Ok(())
+}
+
+fn bench_days(days: &[Day], runs: usize, baseline: &Path, save: bool) -> Result<()> {
+    let mut measured = Vec::with_capacity(days.len());
+    for day in days {
+        let input = match day.find_input() {
+            Some(input) => input,
+            None => {
+                eprintln!("day {:02}: no input, skipping", day.day);
+                continue;
+            }
+        };
+        let content = read_to_string(&input)
+            .with_context(|| format!("Could not read input {}", input.display()))?;
+        let timings = bench::measure(day, &content, runs)
+            .with_context(|| format!("Could not benchmark day {}", day.day))?;
+        measured.push((*day, timings));
+    }
+
+    let saved = Baseline::read(baseline)?;
+    print!("{}", bench::table(&measured, saved.as_ref()));
+
+    if save {
+        let mut saved = saved.unwrap_or_default();
+        for (day, timings) in measured {
+            saved.insert(&day, timings);
+        }
+        saved.write(baseline)?;
+        println!("Saved baseline to {}", baseline.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use anyhow::{bail, Context, Result};
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+const MAIN_RS: &str = include_str!("../templates/main.rs.tmpl");
+
+/// Create the crate for `day` below the workspace `root` and register it with the workspace and
+/// the runner.
+///
+/// Returns all created or modified files.
+pub fn create(root: &Path, day: u8) -> Result<Vec<PathBuf>> {
+    let name = format!("day_{:02}", day);
+    let dir = root.join(&name);
+    if dir.exists() {
+        bail!("{} exists already.", dir.display());
+    }
+
+    let render = |template: &str| {
+        template
+            .replace("{{day}}", &format!("{:02}", day))
+            .replace("{{num}}", &day.to_string())
+    };
+
+    // register first so that nothing is left behind in case the workspace has an unexpected layout
+    let registrations = [
+        (
+            root.join("Cargo.toml"),
+            "\"day_",
+            format!("    \"{}\",", name),
+        ),
+        (
+            root.join("aoc").join("Cargo.toml"),
+            "day_",
+            format!("{} = {{ path = \"../{}\" }}", name, name),
+        ),
+        (
+            root.join("aoc").join("src").join("days.rs"),
+            "Day::of::<day_",
+            format!("    Day::of::<{}::Day{:02}>(),", name, day),
+        ),
+    ];
+    let mut registered = Vec::new();
+    for (path, prefix, line) in registrations {
+        let content =
+            read_to_string(&path).with_context(|| format!("Could not read {}", path.display()))?;
+        let content =
+            register(&content, prefix, &line).with_context(|| format!("In {}", path.display()))?;
+        registered.push((path, content));
+    }
+
+    let files = [
+        (dir.join("Cargo.toml"), render(CARGO_TOML)),
+        (dir.join("src").join("lib.rs"), render(LIB_RS)),
+        (dir.join("src").join("main.rs"), render(MAIN_RS)),
+        (dir.join("debug.txt"), String::new()),
+    ];
+    create_dir_all(dir.join("src"))?;
+    let mut written = Vec::new();
+    for (path, content) in files.into_iter().chain(registered) {
+        write(&path, content).with_context(|| format!("Could not write {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Insert `line` among the (sorted) lines starting with `prefix`.
+fn register(content: &str, prefix: &str, line: &str) -> Result<String> {
+    let mut lines: Vec<_> = content.lines().collect();
+    let matching: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.trim_start().starts_with(prefix))
+        .map(|(idx, l)| (idx, l.trim()))
+        .collect();
+
+    if matching.iter().any(|(_, l)| *l == line.trim()) {
+        bail!("{} is registered already.", line.trim());
+    }
+    let idx = match matching.iter().find(|(_, l)| *l > line.trim()) {
+        Some((idx, _)) => *idx,
+        None => match matching.last() {
+            Some((idx, _)) => idx + 1,
+            None => bail!("Found no existing lines starting with {}", prefix),
+        },
+    };
+
+    lines.insert(idx, line);
+    let mut registered = lines.join("\n");
+    if content.ends_with('\n') {
+        registered.push('\n');
+    }
+    Ok(registered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs::remove_dir_all;
+
+    #[test]
+    fn register_sorted() -> Result<()> {
+        let members = "members = [\n    \"aoc\",\n    \"day_01\",\n    \"day_03\",\n]\n";
+        assert_eq!(
+            register(members, "\"day_", "    \"day_02\",")?,
+            "members = [\n    \"aoc\",\n    \"day_01\",\n    \"day_02\",\n    \"day_03\",\n]\n"
+        );
+        assert_eq!(
+            register(members, "\"day_", "    \"day_04\",")?,
+            "members = [\n    \"aoc\",\n    \"day_01\",\n    \"day_03\",\n    \"day_04\",\n]\n"
+        );
+        assert!(register(members, "\"day_", "    \"day_03\",").is_err());
+        assert!(register(members, "\"dax_", "    \"dax_03\",").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn create_day() -> Result<()> {
+        let root = env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+        create_dir_all(root.join("aoc").join("src"))?;
+        for file in ["Cargo.toml", "aoc/Cargo.toml", "aoc/src/days.rs"] {
+            write(root.join(file), read_to_string(Path::new("..").join(file))?)?;
+        }
+
+        let written = create(&root, 42)?;
+        assert_eq!(written.len(), 7);
+        let lib = read_to_string(root.join("day_42/src/lib.rs"))?;
+        assert!(lib.contains("pub struct Day42;"));
+        assert!(lib.contains("const DAY: u8 = 42;"));
+        assert!(!lib.contains("{{"));
+        assert!(read_to_string(root.join("day_42/debug.txt"))?.is_empty());
+        assert!(read_to_string(root.join("Cargo.toml"))?.contains("    \"day_42\",\n]"));
+        assert!(read_to_string(root.join("aoc/src/days.rs"))?
+            .contains("    Day::of::<day_42::Day42>(),\n];"));
+
+        assert!(create(&root, 42).is_err(), "crate exists already");
+
+        remove_dir_all(&root)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::{Context, Result};
+use aoc_common::{Part, Report};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use crate::days::Day;
+
+/// Known-good answers, keyed by the day's crate directory and the input file name.
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, Expected>>);
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Expected {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Match {
+        part: Part,
+        answer: String,
+    },
+    Mismatch {
+        part: Part,
+        expected: String,
+        got: String,
+    },
+}
+
+impl Answers {
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = read_to_string(path)
+            .with_context(|| format!("Could not read answers {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Invalid answers {}", path.display()))
+    }
+
+    /// Recorded input files (and their answers) for the given day.
+    pub fn for_day(&self, day: &Day) -> impl Iterator<Item = (&str, &Expected)> {
+        let dir = day.dir();
+        self.0
+            .get(dir.to_str().unwrap_or_default())
+            .into_iter()
+            .flat_map(|inputs| inputs.iter().map(|(input, e)| (input.as_str(), e)))
+    }
+}
+
+impl Expected {
+    fn get(&self, part: Part) -> Option<String> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+        .map(|a| a.to_string())
+    }
+
+    /// Parts that have a recorded answer.
+    pub fn parts(&self) -> Vec<Part> {
+        Part::ALL
+            .into_iter()
+            .filter(|p| self.get(*p).is_some())
+            .collect()
+    }
+
+    /// Compare all solved parts in `report` to the recorded answers.
+    pub fn compare(&self, report: &Report) -> Vec<Check> {
+        report
+            .parts
+            .iter()
+            .filter_map(|solved| {
+                let expected = self.get(solved.part)?;
+                Some(if expected == solved.answer {
+                    Check::Match {
+                        part: solved.part,
+                        answer: expected,
+                    }
+                } else {
+                    Check::Mismatch {
+                        part: solved.part,
+                        expected,
+                        got: solved.answer.clone(),
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(num) => write!(f, "{}", num),
+            Answer::Text(text) => write!(f, "{}", text.trim_end()),
+        }
+    }
+}
+
+/// Solve `input` (relative to the day's crate) and check the answers against `expected`.
+pub fn verify(day: &Day, input: &str, expected: &Expected) -> Result<Vec<Check>> {
+    let path = day.dir().join(input);
+    let content =
+        read_to_string(&path).with_context(|| format!("Could not read {}", path.display()))?;
+    let report = (day.run)(&content, &expected.parts())?;
+    Ok(expected.compare(&report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::PartReport;
+    use std::time::Duration;
+
+    #[test]
+    fn compare() -> Result<()> {
+        let answers: Answers = toml::from_str(
+            "[day_13.\"debug.txt\"]\n\
+             part1 = 17\n\
+             part2 = '''\n##\n#.'''\n\
+             [day_13.\"input.txt\"]\n\
+             part1 = 818\n",
+        )?;
+        let day = crate::days::get(13).unwrap();
+        let recorded: BTreeMap<_, _> = answers.for_day(&day).collect();
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded["input.txt"].parts(), vec![Part::One]);
+
+        let report = Report {
+            day: 13,
+            parse: Duration::ZERO,
+            parts: vec![
+                PartReport {
+                    part: Part::One,
+                    answer: "18".to_owned(),
+                    elapsed: Duration::ZERO,
+                },
+                PartReport {
+                    part: Part::Two,
+                    answer: "##\n#.".to_owned(),
+                    elapsed: Duration::ZERO,
+                },
+            ],
+        };
+        assert_eq!(
+            recorded["debug.txt"].compare(&report),
+            vec![
+                Check::Mismatch {
+                    part: Part::One,
+                    expected: "17".to_owned(),
+                    got: "18".to_owned()
+                },
+                Check::Match {
+                    part: Part::Two,
+                    answer: "##\n#.".to_owned()
+                },
+            ]
+        );
+        Ok(())
+    }
+}
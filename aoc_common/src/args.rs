@@ -0,0 +1,126 @@
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::path::PathBuf;
+
+use crate::{cached_input, Format};
+
+/// Input for `day`: `input` if given, otherwise the fetched input from the cache if available,
+/// otherwise `input.txt`.
+pub fn input_path(day: u8, input: Option<PathBuf>) -> PathBuf {
+    input
+        .or_else(|| cached_input(day))
+        .unwrap_or_else(|| PathBuf::from("input.txt"))
+}
+
+/// Command line of a day binary: `[--format text|json] [OPTIONS] [INPUT]`.
+#[derive(Debug, Clone, Default)]
+pub struct Args {
+    /// Output format, if given explicitly.
+    pub format: Option<Format>,
+    pub input: Option<PathBuf>,
+    // day-specific options in the order given, along with their value if they take one
+    options: Vec<(String, Option<String>)>,
+}
+
+impl Args {
+    /// Parse the arguments of the running binary, see `parse`.
+    pub fn from_env(flags: &[&str], options: &[&str]) -> Result<Self> {
+        Self::parse(env::args().skip(1), flags, options)
+    }
+
+    /// Parse `args`, where `flags` are day-specific switches and `options` take a value.
+    ///
+    /// Any other argument starting with `-` is rejected, except for `-` itself (i.e. stdin).
+    pub fn parse<I>(args: I, flags: &[&str], options: &[&str]) -> Result<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let name = arg.as_str();
+            if name == "--format" {
+                let format = args.next().context("Missing value for --format")?;
+                parsed.format = Some(format.parse()?);
+            } else if flags.contains(&name) {
+                parsed.options.push((arg, None));
+            } else if options.contains(&name) {
+                let value = args
+                    .next()
+                    .with_context(|| format!("Missing value for {}", name))?;
+                parsed.options.push((arg, Some(value)));
+            } else if name.starts_with('-') && name != "-" {
+                bail!("Unknown option: {}", name);
+            } else if let Some(input) = parsed.input.replace(PathBuf::from(&arg)) {
+                bail!(
+                    "Only one input allowed, got {} and {}",
+                    input.display(),
+                    arg
+                );
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Whether the switch `name` was given.
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|(option, _)| option == name)
+    }
+
+    /// Value of the option `name`, the last one if it was given several times.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(option, _)| option == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// Input for `day`, see `input_path`.
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        input_path(day, self.input.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Args> {
+        Args::parse(
+            args.split_whitespace().map(String::from),
+            &["--fix"],
+            &["--method"],
+        )
+    }
+
+    #[test]
+    fn options() -> Result<()> {
+        let args = parse("--method sweep --format json in.txt --method points")?;
+        assert_eq!(args.format, Some(Format::Json));
+        assert_eq!(args.input, Some(PathBuf::from("in.txt")));
+        assert_eq!(args.value("--method"), Some("points"));
+        assert!(!args.flag("--fix"));
+
+        let args = parse("--fix -")?;
+        assert!(args.flag("--fix"));
+        assert_eq!(args.value("--fix"), None);
+        assert_eq!(args.input, Some(PathBuf::from("-")));
+        assert_eq!(args.format, None);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() {
+        for args in [
+            "--window 3",
+            "-x",
+            "--method",
+            "--format",
+            "--format xml",
+            "a.txt b.txt",
+        ] {
+            assert!(parse(args).is_err(), "{}", args);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Directory for downloaded puzzle inputs.
+///
+/// `$AOC_CACHE_DIR` if set, otherwise `aoc-2021` in `$XDG_CACHE_HOME` (or `~/.cache`).
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("AOC_CACHE_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    let base = match env::var_os("XDG_CACHE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".cache"),
+    };
+    Some(base.join("aoc-2021"))
+}
+
+/// Location of the input for `day` within the cache directory `dir`.
+pub fn cache_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day_{:02}.txt", day))
+}
+
+/// Previously fetched input for `day`, if there is one.
+pub fn cached_input(day: u8) -> Option<PathBuf> {
+    let path = cache_path(&cache_dir()?, day);
+    path.is_file().then_some(path)
+}
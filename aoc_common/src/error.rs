@@ -0,0 +1,112 @@
+use nom::error::ErrorKind;
+use std::error::Error;
+use std::fmt;
+
+/// Maximum number of characters of the offending line shown in diagnostics.
+const SNIPPET_WIDTH: usize = 60;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A nom parser did not match.
+    Nom(ErrorKind),
+    /// Parsing succeeded, but did not consume the full input.
+    TrailingInput,
+}
+
+/// Malformed puzzle input, located in the original text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    /// 1-based line of the error.
+    pub line: usize,
+    /// 1-based column (in characters) of the error.
+    pub column: usize,
+    /// Excerpt of the offending line around `column`.
+    pub snippet: String,
+    /// Column at which `snippet` starts (0-based).
+    snippet_start: usize,
+}
+
+impl ParseError {
+    /// Error at byte `offset` into `input`.
+    pub fn at(input: &str, offset: usize, kind: ParseErrorKind) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |idx| offset + idx);
+        let text = input[line_start..line_end].trim_end_matches('\r');
+
+        let column = input[line_start..offset].chars().count();
+        let snippet_start = column.saturating_sub(SNIPPET_WIDTH / 2);
+        Self {
+            kind,
+            line: input[..line_start].matches('\n').count() + 1,
+            column: column + 1,
+            snippet: text
+                .chars()
+                .skip(snippet_start)
+                .take(SNIPPET_WIDTH)
+                .collect(),
+            snippet_start,
+        }
+    }
+
+    /// Error at the start of `rest`, which has to be a suffix of `input` (as returned by nom).
+    pub fn at_rest(input: &str, rest: &str, kind: ParseErrorKind) -> Self {
+        Self::at(input, input.len() - rest.len(), kind)
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::Nom(kind) => write!(f, "unexpected input ({})", kind.description()),
+            ParseErrorKind::TrailingInput => write!(f, "unexpected trailing input"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} at line {}, column {}:",
+            self.kind, self.line, self.column
+        )?;
+        writeln!(f, "{}", self.snippet)?;
+        write!(f, "{:>1$}", "^", self.column - self.snippet_start)
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate() {
+        let input = "1,2\n3,x,5\n";
+        let err = ParseError::at(input, 6, ParseErrorKind::Nom(ErrorKind::Digit));
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.snippet, "3,x,5");
+        assert_eq!(
+            err.to_string(),
+            "unexpected input (Digit) at line 2, column 3:\n3,x,5\n  ^"
+        );
+
+        let err = ParseError::at_rest(input, "", ParseErrorKind::TrailingInput);
+        assert_eq!((err.line, err.column), (3, 1));
+
+        let long = "0".repeat(100) + "x";
+        let err = ParseError::at(&long, 100, ParseErrorKind::TrailingInput);
+        assert_eq!(err.column, 101);
+        assert_eq!(err.snippet, "0".repeat(30) + "x");
+        assert!(err.to_string().ends_with(&format!("\n{}^", " ".repeat(30))));
+    }
+}
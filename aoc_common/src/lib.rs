@@ -0,0 +1,144 @@
+use anyhow::Result;
+use nom::{
+    character::complete::{char, digit1, line_ending, satisfy},
+    combinator::{map, map_res, opt, recognize, verify},
+    multi::{many1, separated_list1},
+    sequence::pair,
+    Finish, IResult,
+};
+use std::str::FromStr;
+
+mod args;
+mod cache;
+mod error;
+mod position;
+mod rng;
+mod solution;
+
+pub use args::{input_path, Args};
+pub use cache::{cache_dir, cache_path, cached_input};
+pub use error::{ParseError, ParseErrorKind};
+pub use position::Position;
+pub use rng::Lcg;
+pub use solution::{
+    main, run, run_with, solve, solve_with, Format, Part, PartReport, Report, Runner, Solution,
+};
+
+/// Types that can be parsed from puzzle text.
+pub trait Parseable: Sized {
+    fn parse(i: &str) -> IResult<&str, Self>;
+}
+
+/// Input for bit-level parsers: the remaining bytes and the bit offset into the first byte.
+pub type InputBits<'a> = (&'a [u8], usize);
+
+/// Types that can be parsed from a bit stream (see `nom::bits`).
+pub trait ParseableBits: Sized {
+    fn parse(i: InputBits) -> IResult<InputBits, Self>;
+}
+
+/// Parse the complete input into `T`, see `parse_all_with`.
+pub fn parse_all<T: Parseable>(i: &str) -> Result<T> {
+    parse_all_with(T::parse, i)
+}
+
+/// Run `parser` on the complete input.
+///
+/// Trailing whitespace (e.g. the final newline) is ignored, any other leftover input is reported
+/// as an error. Errors are `ParseError`s located in `i`.
+pub fn parse_all_with<'a, T, P>(mut parser: P, i: &'a str) -> Result<T>
+where
+    P: FnMut(&'a str) -> IResult<&'a str, T>,
+{
+    match parser(i).finish() {
+        Ok((rest, parsed)) => {
+            let rest = rest.trim_start();
+            if !rest.is_empty() {
+                return Err(ParseError::at_rest(i, rest, ParseErrorKind::TrailingInput).into());
+            }
+            Ok(parsed)
+        }
+        Err(e) => Err(ParseError::at_rest(i, e.input, ParseErrorKind::Nom(e.code)).into()),
+    }
+}
+
+/// Unsigned decimal number.
+pub fn num1<T: FromStr>(i: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(i)
+}
+
+/// Decimal number with optional leading minus sign.
+pub fn signed<T: FromStr>(i: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(i)
+}
+
+/// Single decimal digit.
+pub fn digit<T: From<u8>>(i: &str) -> IResult<&str, T> {
+    map(satisfy(|c| c.is_ascii_digit()), |c| T::from(c as u8 - b'0'))(i)
+}
+
+/// Comma-separated list of at least one element.
+pub fn comma_list<'a, T, P>(elem: P) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>>
+where
+    P: FnMut(&'a str) -> IResult<&'a str, T>,
+{
+    separated_list1(char(','), elem)
+}
+
+/// Rectangular grid with one row per line, cells are parsed by `cell` without separators.
+pub fn grid<'a, T, P>(cell: P) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<T>>>
+where
+    P: FnMut(&'a str) -> IResult<&'a str, T>,
+{
+    verify(
+        separated_list1(line_ending, many1(cell)),
+        |rows: &Vec<Vec<T>>| rows.iter().all(|row| row.len() == rows[0].len()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(num1::<u64>("123,4"), Ok((",4", 123)));
+        assert_eq!(signed::<i64>("-42 "), Ok((" ", -42)));
+        assert_eq!(signed::<i64>("42"), Ok(("", 42)));
+        assert_eq!(digit::<usize>("42"), Ok(("2", 4)));
+        assert!(num1::<u8>("300").is_err(), "overflow must not panic");
+    }
+
+    #[test]
+    fn lists() {
+        assert_eq!(
+            comma_list(num1::<u64>)("3,4,5\n"),
+            Ok(("\n", vec![3, 4, 5]))
+        );
+        assert_eq!(
+            grid(digit::<u64>)("12\n34\n"),
+            Ok(("\n", vec![vec![1, 2], vec![3, 4]]))
+        );
+        assert!(
+            grid(digit::<u64>)("12\n3").is_err(),
+            "grid must be rectangular"
+        );
+    }
+
+    #[test]
+    fn leftover_input() {
+        assert_eq!(
+            parse_all_with(comma_list(num1::<u64>), "1,2\n").unwrap(),
+            vec![1, 2]
+        );
+        let err = parse_all_with(comma_list(num1::<u64>), "1,2\nfoo").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!(err.kind, ParseErrorKind::TrailingInput);
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.snippet, "foo");
+
+        let err = parse_all_with(comma_list(num1::<u64>), "1,2,\n").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (1, 4));
+    }
+}
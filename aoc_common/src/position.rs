@@ -0,0 +1,118 @@
+use nom::{character::complete::char, sequence::tuple, IResult};
+use std::ops::{Add, Neg, Sub};
+
+use crate::{signed, Parseable};
+
+/// Point (or offset) in 3D space.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy, Default)]
+pub struct Position {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Position {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(&self, other: &Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+}
+
+impl Add for &Position {
+    type Output = Position;
+
+    fn add(self, other: Self) -> Position {
+        Position {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl Add for Position {
+    type Output = Position;
+
+    fn add(self, other: Self) -> Position {
+        Position {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl Sub for &Position {
+    type Output = Position;
+
+    fn sub(self, other: Self) -> Position {
+        Position {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl Sub for Position {
+    type Output = Position;
+
+    fn sub(self, other: Self) -> Position {
+        Position {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl Neg for Position {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Position {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl Neg for &Position {
+    type Output = Position;
+
+    fn neg(self) -> Self::Output {
+        Position {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+/// Comma-separated coordinates, e.g. `-1,2,3`.
+impl Parseable for Position {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let comma = || char(',');
+        let (i, (x, _, y, _, z)) = tuple((signed, comma(), signed, comma(), signed))(i)?;
+        Ok((i, Self { x, y, z }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let (_, a) = Position::parse("1,-2,3").unwrap();
+        let b = Position::new(-4, 5, 6);
+        assert_eq!(a + b, Position::new(-3, 3, 9));
+        assert_eq!(a - b, Position::new(5, -7, -3));
+        assert_eq!(-a, Position::new(-1, 2, -3));
+        assert_eq!(a.manhattan(&b), 15);
+    }
+}
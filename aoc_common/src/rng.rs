@@ -0,0 +1,20 @@
+/// Deterministic pseudo-random numbers for generated test cases (Knuth's MMIX LCG).
+#[derive(Debug, Clone)]
+pub struct Lcg {
+    state: u64,
+}
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Next number in `0..max`.
+    pub fn below(&mut self, max: u64) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.state >> 33) % max
+    }
+}
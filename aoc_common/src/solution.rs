@@ -0,0 +1,283 @@
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::Args;
+
+/// Solver for a single day of the advent calendar.
+pub trait Solution {
+    /// Day of the advent calendar (1-based).
+    const DAY: u8;
+
+    type Input;
+    type Answer1: fmt::Display;
+    type Answer2: fmt::Display;
+
+    fn parse(i: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => bail!("Invalid part: {} (must be 1 or 2)", s),
+        }
+    }
+}
+
+/// Output format for reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable text, one line per part.
+    #[default]
+    Text,
+    /// One JSON object per part and line: `{day, part, answer, elapsed_ms, input}`.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => bail!("Invalid format: {} (must be text or json)", s),
+        }
+    }
+}
+
+/// Answers and timings of running a solution on one input.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u8,
+    pub parse: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+/// Type-erased entry point to `run`, so that solutions for all days can be stored side by side.
+pub type Runner = fn(&str, &[Part]) -> Result<Report>;
+
+/// Parse `i` and solve the requested `parts`, timing each phase separately.
+pub fn run<S: Solution>(i: &str, parts: &[Part]) -> Result<Report> {
+    run_with::<S, _>(i, parts, |input, part| match part {
+        Part::One => Ok(S::part1(input)?.to_string()),
+        Part::Two => Ok(S::part2(input)?.to_string()),
+    })
+}
+
+/// Like `run`, but the parts are solved by `solve_part` instead of the solution's own methods.
+pub fn run_with<S, F>(i: &str, parts: &[Part], solve_part: F) -> Result<Report>
+where
+    S: Solution,
+    F: Fn(&S::Input, Part) -> Result<String>,
+{
+    let start = Instant::now();
+    let input = S::parse(i).with_context(|| format!("Could not parse input for day {}", S::DAY))?;
+    let parse = start.elapsed();
+
+    let mut reports = Vec::with_capacity(parts.len());
+    for part in parts {
+        let start = Instant::now();
+        let answer = solve_part(&input, *part)?;
+        reports.push(PartReport {
+            part: *part,
+            answer,
+            elapsed: start.elapsed(),
+        });
+    }
+
+    Ok(Report {
+        day: S::DAY,
+        parse,
+        parts: reports,
+    })
+}
+
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    day: u8,
+    part: u8,
+    answer: &'a str,
+    elapsed_ms: f64,
+    input: &'a str,
+}
+
+impl Report {
+    /// Render the report in the given format, `input` is the path the answers were computed for.
+    pub fn render(&self, format: Format, input: &Path) -> Result<String> {
+        match format {
+            Format::Text => Ok(self.to_string()),
+            Format::Json => {
+                let input = input.to_string_lossy();
+                let mut rendered = String::new();
+                for part in self.parts.iter() {
+                    rendered += &serde_json::to_string(&JsonRecord {
+                        day: self.day,
+                        part: part.part.number(),
+                        answer: &part.answer,
+                        elapsed_ms: part.elapsed.as_secs_f64() * 1e3,
+                        input: &input,
+                    })?;
+                    rendered.push('\n');
+                }
+                Ok(rendered)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for part in self.parts.iter() {
+            write!(f, "day {:02} part {}: ", self.day, part.part)?;
+            // multi-line answers (e.g. ASCII art) start on their own line
+            if part.answer.contains('\n') {
+                writeln!(f, "({:.2?})\n{}", part.elapsed, part.answer)?;
+            } else {
+                writeln!(f, "{} ({:.2?})", part.answer, part.elapsed)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Main function shared by all day binaries: solve both parts for the given input.
+///
+/// Usage: `day_NN [--format text|json] [INPUT]`. Without explicit input, the fetched input from
+/// the cache is used if available, otherwise `input.txt`.
+pub fn main<S: Solution>() -> Result<()> {
+    solve::<S>(&Args::from_env(&[], &[])?)
+}
+
+/// Solve both parts for the input given in `args` and print the report.
+pub fn solve<S: Solution>(args: &Args) -> Result<()> {
+    solve_with::<S, _>(args, |input, part| match part {
+        Part::One => Ok(S::part1(input)?.to_string()),
+        Part::Two => Ok(S::part2(input)?.to_string()),
+    })
+}
+
+/// Like `solve`, but the parts are solved by `solve_part`, see `run_with`.
+pub fn solve_with<S, F>(args: &Args, solve_part: F) -> Result<()>
+where
+    S: Solution,
+    F: Fn(&S::Input, Part) -> Result<String>,
+{
+    let input = args.input_path(S::DAY);
+    let format = args.format.unwrap_or_default();
+    if format == Format::Text {
+        println!("Input: {}", input.display());
+    }
+    let content = read_to_string(&input)
+        .with_context(|| format!("Could not read input {}", input.display()))?;
+
+    print!(
+        "{}",
+        run_with::<S, _>(&content, &Part::ALL, solve_part)?.render(format, &input)?
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{comma_list, num1, parse_all_with};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+
+        type Input = Vec<u64>;
+        type Answer1 = u64;
+        type Answer2 = usize;
+
+        fn parse(i: &str) -> Result<Self::Input> {
+            parse_all_with(comma_list(num1), i)
+        }
+
+        fn part1(input: &Self::Input) -> Result<u64> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(input: &Self::Input) -> Result<usize> {
+            Ok(input.len())
+        }
+    }
+
+    #[test]
+    fn run_parts() -> Result<()> {
+        let report = run::<Sum>("1,2,3\n", &[Part::Two])?;
+        assert_eq!(report.parts.len(), 1);
+        assert_eq!(report.parts[0].part, Part::Two);
+        assert_eq!(report.parts[0].answer, "3");
+
+        let report = run::<Sum>("1,2,3\n", &Part::ALL)?;
+        assert_eq!(report.parts[0].answer, "6");
+        assert!(run::<Sum>("1,a\n", &Part::ALL).is_err());
+
+        let report = run_with::<Sum, _>("1,2,3\n", &Part::ALL, |input, part| {
+            Ok(format!("{}:{}", part, input.len()))
+        })?;
+        assert_eq!(report.parts[0].answer, "1:3");
+        assert_eq!(report.parts[1].answer, "2:3");
+        Ok(())
+    }
+
+    #[test]
+    fn render_json() -> Result<()> {
+        let report = run::<Sum>("1,2,3\n", &Part::ALL)?;
+        let rendered = report.render(Format::Json, Path::new("day_00/input.txt"))?;
+        let records: Vec<serde_json::Value> = rendered
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1]["day"], 0);
+        assert_eq!(records[1]["part"], 2);
+        assert_eq!(records[1]["answer"], "3");
+        assert_eq!(records[1]["input"], "day_00/input.txt");
+        assert!(records[1]["elapsed_ms"].is_f64());
+        Ok(())
+    }
+}
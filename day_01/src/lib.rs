@@ -0,0 +1,109 @@
+use anyhow::{bail, Context, Result};
+use aoc_common::{num1, parse_all_with, Solution};
+use nom::{character::complete::line_ending, multi::separated_list1};
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        parse_all_with(separated_list1(line_ending, num1), i)
+    }
+
+    fn part1(depths: &Self::Input) -> Result<usize> {
+        sliding_window_increases(depths.iter().copied(), 1)
+    }
+
+    fn part2(depths: &Self::Input) -> Result<usize> {
+        sliding_window_increases(depths.iter().copied(), 3)
+    }
+}
+
+/// Count how often the sum over `window_size` consecutive depths increases.
+///
+/// Neighbouring windows share all but their first/last element, so only those need to be
+/// compared and at most `window_size` depths are kept in memory.
+pub fn sliding_window_increases<I>(depths: I, window_size: usize) -> Result<usize>
+where
+    I: IntoIterator<Item = usize>,
+{
+    if window_size == 0 {
+        bail!("Window size must be at least 1.");
+    }
+    let mut window = VecDeque::with_capacity(window_size);
+    let mut count_increases = 0;
+
+    for num in depths {
+        if window.len() == window_size {
+            let dropped = window.pop_front().unwrap();
+            if dropped < num {
+                count_increases += 1;
+            }
+        }
+        window.push_back(num);
+    }
+    Ok(count_increases)
+}
+
+/// Depths read line by line from `reader`, e.g. a file or stdin.
+pub fn read_depths<R: BufRead>(reader: R) -> impl Iterator<Item = Result<usize>> {
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.as_ref().is_ok_and(|l| l.trim().is_empty()))
+        .map(|(idx, line)| {
+            let line = line.with_context(|| format!("Could not read line {}", idx + 1))?;
+            line.trim()
+                .parse()
+                .with_context(|| format!("Invalid depth in line {}: {}", idx + 1, line))
+        })
+}
+
+/// Streaming variant of `sliding_window_increases` that never holds the whole input in memory.
+pub fn stream_window_increases<R: BufRead>(reader: R, window_size: usize) -> Result<usize> {
+    let mut error = None;
+    let depths = read_depths(reader).map_while(|depth| depth.map_err(|e| error = Some(e)).ok());
+    let count = sliding_window_increases(depths, window_size)?;
+    match error {
+        Some(e) => Err(e),
+        None => Ok(count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    #[test]
+    fn test_parts() -> Result<()> {
+        let depths = Day01::parse(&std::fs::read_to_string("debug.txt")?)?;
+
+        assert_eq!(Day01::part1(&depths)?, 7);
+        assert_eq!(Day01::part2(&depths)?, 5);
+        assert_eq!(sliding_window_increases(depths.iter().copied(), 10)?, 0);
+        assert!(sliding_window_increases(depths, 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream() -> Result<()> {
+        let reader = || -> Result<_> { Ok(BufReader::new(File::open("debug.txt")?)) };
+
+        assert_eq!(stream_window_increases(reader()?, 1)?, 7);
+        assert_eq!(stream_window_increases(reader()?, 3)?, 5);
+        assert_eq!(stream_window_increases(&b"1\n3\n\n2\n"[..], 2)?, 1);
+
+        let err = stream_window_increases(&b"1\n2\nthree\n4\n"[..], 1).unwrap_err();
+        assert!(err.to_string().contains("line 3"), "{}", err);
+        Ok(())
+    }
+}
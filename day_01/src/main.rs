@@ -1,76 +1,51 @@
 use anyhow::{Context, Result};
-use std::env;
+use aoc_common::{solve, Args, Part, PartReport, Report, Solution};
+use day_01::{stream_window_increases, Day01};
 use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::{Path, PathBuf};
-
+use std::io::{stdin, BufReader};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Usage: `day_01 [--format text|json] [--window SIZE] [INPUT|-]`
+///
+/// With `--window`, only the increases for the given window size are counted while streaming the
+/// input (`-` reads from stdin). The count is reported as part 2, whose window size it replaces.
+/// Otherwise both parts are solved as for every other day.
 fn main() -> Result<()> {
-    let input = PathBuf::from(env::args().nth(1).with_context(|| "No input provided!")?);
-    println!("Input: {}", input.display());
-    part1(&input)?;
-    part2(&input)?;
-    Ok(())
-}
-
-fn part1(input: &Path) -> Result<()> {
-    let mut prev = None;
-    let mut count_increases: usize = 0;
-
-    for (i, line) in io::BufReader::new(File::open(input)?).lines().enumerate() {
-        let num = line
-            .with_context(|| format!("Could not read line {}", i))?
-            .parse::<usize>()?;
-
-        if let Some(prev) = prev {
-            if prev < num {
-                count_increases += 1;
-            }
-        }
-
-        prev = Some(num);
-    }
-    println!("Part 1: {} increases", count_increases);
-    Ok(())
-}
-
-fn part2(input: &Path) -> Result<()> {
-    let mut count_increases: usize = 0;
-
-    let mut lines = io::BufReader::new(File::open(input)?).lines().enumerate();
-
-    let parse = |line: String| line.parse::<usize>();
-    let first = lines
-        .next()
-        .with_context(|| "First line missing.")
-        .map(|t| t.1)?
-        .map(parse)??;
-    let second = lines
-        .next()
-        .with_context(|| "Second line missing")
-        .map(|t| t.1)?
-        .map(parse)??;
-    let third = lines
-        .next()
-        .with_context(|| "Third line missing")
-        .map(|t| t.1)?
-        .map(parse)??;
-
-    let mut window = vec![third, second, first];
-
-    for (i, line) in io::BufReader::new(File::open(input)?).lines().enumerate() {
-        let num = line
-            .with_context(|| format!("Could not read line {}", i))?
-            .parse::<usize>()?;
-
-        let sum_prev: usize = window.iter().sum();
-        window.pop();
-        window.insert(0, num);
-        let sum_now: usize = window.iter().sum();
-
-        if sum_prev < sum_now {
-            count_increases += 1;
+    let args = Args::from_env(&[], &["--window"])?;
+    let Some(size) = args.value("--window") else {
+        return solve::<Day01>(&args);
+    };
+    let window_size = size
+        .parse()
+        .with_context(|| format!("Invalid window size: {}", size))?;
+
+    let start = Instant::now();
+    let (input, count) = match args.input.as_deref() {
+        Some(path) if path.as_os_str() == "-" => (
+            PathBuf::from("-"),
+            stream_window_increases(stdin().lock(), window_size)?,
+        ),
+        _ => {
+            let input = args.input_path(Day01::DAY);
+            let file = File::open(&input)
+                .with_context(|| format!("Could not read input {}", input.display()))?;
+            let count = stream_window_increases(BufReader::new(file), window_size)?;
+            (input, count)
         }
-    }
-    println!("Part 2: {} increases", count_increases);
+    };
+    let report = Report {
+        day: Day01::DAY,
+        parse: Duration::ZERO,
+        parts: vec![PartReport {
+            part: Part::Two,
+            answer: count.to_string(),
+            elapsed: start.elapsed(),
+        }],
+    };
+    print!(
+        "{}",
+        report.render(args.format.unwrap_or_default(), &input)?
+    );
     Ok(())
 }
@@ -0,0 +1,397 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Result};
+use aoc_common::{num1, parse_all_with, signed, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace0, multispace1, none_of, one_of,
+        space0, space1,
+    },
+    combinator::{map, map_res, value},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    ErrorConvert, Finish, IResult,
+};
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Program;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        Command::from(i)
+    }
+
+    fn part1(program: &Self::Input) -> Result<i64> {
+        let sub: Simple = execute(program, None)?;
+        mul(sub.pos, sub.depth)
+    }
+
+    fn part2(program: &Self::Input) -> Result<i64> {
+        let sub: Aiming = execute(program, None)?;
+        mul(sub.pos, sub.depth)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Forward(i64),
+    Back(i64),
+    Up(i64),
+    Down(i64),
+    /// Return to the initial state of the submarine.
+    Reset,
+    /// Execute the contained commands the given number of times.
+    Repeat(u64, Program),
+}
+
+pub type Program = Vec<Command>;
+
+impl Parseable for Command {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        alt((
+            Self::parse_steps("forward", Self::Forward),
+            Self::parse_steps("back", Self::Back),
+            Self::parse_steps("up", Self::Up),
+            Self::parse_steps("down", Self::Down),
+            value(Self::Reset, tag("reset")),
+            Self::parse_repeat,
+        ))(i)
+    }
+}
+
+impl Command {
+    fn from(i: &str) -> Result<Program> {
+        parse_all_with(preceded(multispace0, program), i)
+    }
+
+    fn parse_steps<'a>(
+        name: &'static str,
+        command: fn(i64) -> Self,
+    ) -> impl FnMut(&'a str) -> IResult<&'a str, Self> {
+        map(preceded(pair(tag(name), space1), signed), command)
+    }
+
+    fn parse_repeat(i: &str) -> IResult<&str, Self> {
+        let (i, times) = delimited(pair(tag("repeat"), space1), num1, multispace0)(i)?;
+        let (i, body) = delimited(
+            pair(char('{'), multispace0),
+            program,
+            pair(multispace0, char('}')),
+        )(i)?;
+        Ok((i, Self::Repeat(times, body)))
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Forward(steps) => write!(f, "forward {}", steps),
+            Self::Back(steps) => write!(f, "back {}", steps),
+            Self::Up(steps) => write!(f, "up {}", steps),
+            Self::Down(steps) => write!(f, "down {}", steps),
+            Self::Reset => write!(f, "reset"),
+            Self::Repeat(times, body) => {
+                write!(f, "repeat {} {{", times)?;
+                for command in body.iter() {
+                    write!(f, " {}", command)?;
+                }
+                write!(f, " }}")
+            }
+        }
+    }
+}
+
+fn program(i: &str) -> IResult<&str, Program> {
+    separated_list1(multispace1, Command::parse)(i)
+}
+
+fn add(a: i64, b: i64) -> Result<i64> {
+    a.checked_add(b)
+        .with_context(|| format!("Overflow in {} + {}", a, b))
+}
+
+fn sub(a: i64, b: i64) -> Result<i64> {
+    a.checked_sub(b)
+        .with_context(|| format!("Overflow in {} - {}", a, b))
+}
+
+fn mul(a: i64, b: i64) -> Result<i64> {
+    a.checked_mul(b)
+        .with_context(|| format!("Overflow in {} * {}", a, b))
+}
+
+/// State of the submarine, each implementation interprets the movement commands differently.
+///
+/// Movements fail instead of overflowing.
+pub trait Submarine: Default + fmt::Display {
+    fn forward(&mut self, steps: i64) -> Result<()>;
+    fn up(&mut self, steps: i64) -> Result<()>;
+    fn down(&mut self, steps: i64) -> Result<()>;
+
+    fn back(&mut self, steps: i64) -> Result<()> {
+        self.forward(sub(0, steps)?)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Up and down change the depth directly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Simple {
+    pub pos: i64,
+    pub depth: i64,
+}
+
+impl Submarine for Simple {
+    fn forward(&mut self, steps: i64) -> Result<()> {
+        self.pos = add(self.pos, steps)?;
+        Ok(())
+    }
+
+    fn up(&mut self, steps: i64) -> Result<()> {
+        self.depth = sub(self.depth, steps)?;
+        Ok(())
+    }
+
+    fn down(&mut self, steps: i64) -> Result<()> {
+        self.depth = add(self.depth, steps)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Simple {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "pos {}, depth {}", self.pos, self.depth)
+    }
+}
+
+/// Up and down change the aim, depth only changes when moving.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Aiming {
+    pub pos: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+impl Submarine for Aiming {
+    fn forward(&mut self, steps: i64) -> Result<()> {
+        self.pos = add(self.pos, steps)?;
+        self.depth = add(self.depth, mul(steps, self.aim)?)?;
+        Ok(())
+    }
+
+    fn up(&mut self, steps: i64) -> Result<()> {
+        self.aim = sub(self.aim, steps)?;
+        Ok(())
+    }
+
+    fn down(&mut self, steps: i64) -> Result<()> {
+        self.aim = add(self.aim, steps)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Aiming {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "pos {}, depth {}, aim {}",
+            self.pos, self.depth, self.aim
+        )
+    }
+}
+
+/// Most commands a program may execute, counting each repetition separately.
+pub const MAX_STEPS: u64 = 100_000_000;
+
+/// Number of commands executed by `program`, `None` if it does not fit into a `u64`.
+fn count_steps(program: &[Command]) -> Option<u64> {
+    program.iter().try_fold(0u64, |count, command| {
+        let steps = match command {
+            Command::Repeat(times, body) => count_steps(body)?.checked_mul(*times)?,
+            _ => 1,
+        };
+        count.checked_add(steps)
+    })
+}
+
+/// Run `program` on a fresh submarine.
+///
+/// If `trace` is given, every executed command is written to it along with the resulting state as
+/// soon as it has been executed. Programs executing more than `MAX_STEPS` commands are rejected
+/// before running them.
+pub fn execute<S: Submarine>(
+    program: &[Command],
+    mut trace: Option<&mut dyn io::Write>,
+) -> Result<S> {
+    match count_steps(program) {
+        Some(steps) if steps <= MAX_STEPS => {}
+        _ => bail!("Program executes more than {} commands.", MAX_STEPS),
+    }
+    let mut sub = S::default();
+    run(program, &mut sub, &mut trace)?;
+    Ok(sub)
+}
+
+fn run<S: Submarine>(
+    program: &[Command],
+    sub: &mut S,
+    trace: &mut Option<&mut dyn io::Write>,
+) -> Result<()> {
+    for command in program.iter() {
+        match command {
+            Command::Forward(steps) => sub.forward(*steps)?,
+            Command::Back(steps) => sub.back(*steps)?,
+            Command::Up(steps) => sub.up(*steps)?,
+            Command::Down(steps) => sub.down(*steps)?,
+            Command::Reset => sub.reset(),
+            Command::Repeat(times, body) => {
+                for _ in 0..*times {
+                    run(body, sub, trace)?;
+                }
+                continue;
+            }
+        }
+        if let Some(trace) = trace {
+            writeln!(trace, "{:<12} -> {}", command.to_string(), sub)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEBUG_DATA: &str = "forward 5\n\
+                              down 5\n\
+                              forward 8\n\
+                              up 3\n\
+                              down 8\n\
+                              forward 2\n";
+
+    #[test]
+    fn test_part1() -> Result<()> {
+        let program = Command::from(DEBUG_DATA)?;
+        assert_eq!(program.len(), 6, "did not parse all instructions");
+
+        let sub: Simple = execute(&program, None)?;
+        assert_eq!(sub.pos, 15, "pos does not match");
+        assert_eq!(sub.depth, 10, "depth does not match");
+        assert_eq!(Day02::part1(&program)?, 150);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> Result<()> {
+        let program = Command::from(DEBUG_DATA)?;
+
+        let sub: Aiming = execute(&program, None)?;
+        assert_eq!(sub.pos, 15, "pos does not match");
+        assert_eq!(sub.depth, 60, "depth does not match");
+        assert_eq!(Day02::part2(&program)?, 900);
+        Ok(())
+    }
+
+    #[test]
+    fn test_extended_commands() -> Result<()> {
+        let program = Command::from(
+            "down 2\n\
+             repeat 3 {\n    forward 1\n    repeat 2 { down 1 }\n}\n\
+             back 2\n",
+        )?;
+        assert_eq!(program.len(), 3);
+        assert_eq!(
+            program[1],
+            Command::Repeat(
+                3,
+                vec![
+                    Command::Forward(1),
+                    Command::Repeat(2, vec![Command::Down(1)])
+                ]
+            )
+        );
+
+        let sub: Simple = execute(&program, None)?;
+        assert_eq!(sub, Simple { pos: 1, depth: 8 });
+
+        // aim 2, 4, 6 for the three forward steps, ending at aim 8 when moving back
+        let sub: Aiming = execute(&program, None)?;
+        assert_eq!(
+            sub,
+            Aiming {
+                pos: 1,
+                depth: -4,
+                aim: 8
+            }
+        );
+
+        let sub: Aiming = execute(&Command::from("forward 3\nreset\ndown 1\n")?, None)?;
+        assert_eq!(
+            sub,
+            Aiming {
+                pos: 0,
+                depth: 0,
+                aim: 1
+            }
+        );
+
+        assert!(Command::from("repeat 2 { forward 1\n").is_err());
+        assert!(Command::from("sideways 1\n").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace() -> Result<()> {
+        let mut trace = Vec::new();
+        let program = Command::from("down 1\nrepeat 2 { forward 3 }\nreset\n")?;
+        execute::<Aiming>(&program, Some(&mut trace))?;
+        assert_eq!(
+            String::from_utf8(trace)?,
+            "down 1       -> pos 0, depth 0, aim 1\n\
+             forward 3    -> pos 3, depth 3, aim 1\n\
+             forward 3    -> pos 6, depth 6, aim 1\n\
+             reset        -> pos 0, depth 0, aim 0\n"
+        );
+        Ok(())
+    }
+    #[test]
+    fn test_overflow() -> Result<()> {
+        let program = Command::from("down 9223372036854775807\ndown 1\n")?;
+        assert!(execute::<Simple>(&program, None).is_err());
+
+        // the state before the failing command is still traced
+        let mut trace = Vec::new();
+        let program = Command::from("down 2\nforward 4611686018427387904\n")?;
+        assert!(execute::<Aiming>(&program, Some(&mut trace)).is_err());
+        assert_eq!(
+            String::from_utf8(trace)?,
+            "down 2       -> pos 0, depth 0, aim 2\n"
+        );
+
+        let program = Command::from("forward 4294967296\ndown 4294967296\n")?;
+        assert!(Day02::part1(&program).is_err());
+        assert!(execute::<Simple>(&Command::from("back -9223372036854775808\n")?, None).is_err());
+
+        let program = Command::from("repeat 1000000 { repeat 1000000 { forward 0 } }\n")?;
+        let err = execute::<Simple>(&program, None).unwrap_err();
+        assert!(err.to_string().contains("more than"), "{}", err);
+        let program = Command::from("repeat 18446744073709551615 { repeat 2 { up 0 } }\n")?;
+        assert!(execute::<Simple>(&program, None).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,358 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Result};
+use aoc_common::{parse_all, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{alpha1, anychar, char, digit1, line_ending, none_of, one_of, space0},
+    combinator::{map, map_res, value, verify},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    ErrorConvert, Finish, IResult,
+};
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Diagnostics;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        Diagnostics::from(i)
+    }
+
+    fn part1(diag: &Self::Input) -> Result<u64> {
+        power_consumption(&diag.gamma(), &diag.epsilon())
+    }
+
+    fn part2(diag: &Self::Input) -> Result<u64> {
+        let trie = diag.trie();
+        power_consumption(&trie.oxygen_generator_rating(), &trie.co2_scrubber_rating())
+    }
+}
+
+fn power_consumption(a: &BitString, b: &BitString) -> Result<u64> {
+    a.to_u64()?
+        .checked_mul(b.to_u64()?)
+        .with_context(|| format!("{} * {} overflows", a, b))
+}
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Number of words needed to store `width` bits.
+fn num_words(width: usize) -> usize {
+    width.div_ceil(WORD_BITS)
+}
+
+/// Bit string of arbitrary width, the first bit is the most significant one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitString {
+    words: Vec<u64>,
+    width: usize,
+}
+
+impl BitString {
+    fn from_bits<I: IntoIterator<Item = bool>>(bits: I) -> Self {
+        let mut words = Vec::new();
+        let mut width = 0;
+        for bit in bits {
+            if width % WORD_BITS == 0 {
+                words.push(0);
+            }
+            if bit {
+                words[width / WORD_BITS] |= 1 << (width % WORD_BITS);
+            }
+            width += 1;
+        }
+        Self { words, width }
+    }
+
+    fn get(&self, pos: usize) -> bool {
+        is_bit_set(&self.words, pos)
+    }
+
+    /// Numeric value, if it fits into 64 bits.
+    pub fn to_u64(&self) -> Result<u64> {
+        let mut value: u64 = 0;
+        for pos in 0..self.width {
+            if value.leading_zeros() == 0 {
+                bail!("{} does not fit into 64 bits", self);
+            }
+            value = (value << 1) | self.get(pos) as u64;
+        }
+        Ok(value)
+    }
+}
+
+impl fmt::Display for BitString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for pos in 0..self.width {
+            write!(f, "{}", if self.get(pos) { '1' } else { '0' })?;
+        }
+        Ok(())
+    }
+}
+
+fn is_bit_set(words: &[u64], pos: usize) -> bool {
+    words[pos / WORD_BITS] & (1 << (pos % WORD_BITS)) > 0
+}
+
+/// Report of equally wide bit strings, stored back to back in a single bitset.
+pub struct Diagnostics {
+    words: Vec<u64>,
+    width: usize,
+    len: usize,
+}
+
+impl Parseable for Diagnostics {
+    fn parse(i: &str) -> IResult<&str, Diagnostics> {
+        let (i, lines) = verify(
+            separated_list1(line_ending, take_while1(|c| c == '0' || c == '1')),
+            |lines: &[&str]| lines.iter().all(|l| l.len() == lines[0].len()),
+        )(i)?;
+
+        let width = lines[0].len();
+        let stride = num_words(width);
+        let mut words = vec![0; stride * lines.len()];
+        for (line, chunk) in lines.iter().zip(words.chunks_mut(stride)) {
+            for (pos, c) in line.bytes().enumerate() {
+                if c == b'1' {
+                    chunk[pos / WORD_BITS] |= 1 << (pos % WORD_BITS);
+                }
+            }
+        }
+        Ok((
+            i,
+            Self {
+                words,
+                width,
+                len: lines.len(),
+            },
+        ))
+    }
+}
+
+impl Diagnostics {
+    fn from(i: &str) -> Result<Self> {
+        parse_all(i)
+    }
+
+    fn lines(&self) -> impl Iterator<Item = &[u64]> {
+        self.words.chunks(num_words(self.width))
+    }
+
+    /// Number of set bits for each position, counted in a single pass over the report.
+    fn count_ones(&self) -> Vec<usize> {
+        let mut ones = vec![0; self.width];
+        for line in self.lines() {
+            for (idx, word) in line.iter().enumerate() {
+                let mut word = *word;
+                while word != 0 {
+                    ones[idx * WORD_BITS + word.trailing_zeros() as usize] += 1;
+                    // clear lowest set bit
+                    word &= word - 1;
+                }
+            }
+        }
+        ones
+    }
+
+    /// Whether each bit is set in more than half of all lines (ties count as unset).
+    fn most_common_bits(&self) -> Vec<bool> {
+        self.count_ones()
+            .into_iter()
+            .map(|ones| ones * 2 > self.len)
+            .collect()
+    }
+
+    fn gamma(&self) -> BitString {
+        BitString::from_bits(self.most_common_bits())
+    }
+
+    fn epsilon(&self) -> BitString {
+        BitString::from_bits(self.most_common_bits().into_iter().map(|b| !b))
+    }
+
+    fn trie(&self) -> Trie {
+        let mut trie = Trie::new();
+        for line in self.lines() {
+            trie.insert((0..self.width).map(|pos| is_bit_set(line, pos)));
+        }
+        trie
+    }
+}
+
+/// Binary trie over all lines of a report, counting the lines below each node.
+///
+/// Filtering by bit criteria becomes a single walk from the root instead of repeatedly
+/// partitioning the remaining lines.
+struct Trie {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    count: usize,
+    /// Indices of the children for bit 0 and 1, the root (index 0) is nobody's child.
+    children: [usize; 2],
+}
+
+impl Trie {
+    fn new() -> Self {
+        Self {
+            nodes: vec![Node::default()],
+        }
+    }
+
+    fn insert<I: IntoIterator<Item = bool>>(&mut self, bits: I) {
+        let mut current = 0;
+        self.nodes[current].count += 1;
+        for bit in bits {
+            let mut next = self.nodes[current].children[bit as usize];
+            if next == 0 {
+                next = self.nodes.len();
+                self.nodes.push(Node::default());
+                self.nodes[current].children[bit as usize] = next;
+            }
+            self.nodes[next].count += 1;
+            current = next;
+        }
+    }
+
+    fn count(&self, node: usize) -> usize {
+        if node == 0 {
+            0
+        } else {
+            self.nodes[node].count
+        }
+    }
+
+    /// Follow the bits chosen by `criteria` (given the count of zeros and ones among the remaining
+    /// lines) until a single line remains.
+    fn filter_by(&self, criteria: Criteria) -> BitString {
+        let mut current = 0;
+        let mut bits = Vec::new();
+        loop {
+            let [zero, one] = self.nodes[current].children;
+            let bit = match (self.count(zero), self.count(one)) {
+                (0, 0) => break,
+                (_, 0) => false,
+                (0, _) => true,
+                (zeros, ones) => criteria(zeros, ones),
+            };
+            bits.push(bit);
+            current = self.nodes[current].children[bit as usize];
+        }
+        BitString::from_bits(bits)
+    }
+
+    fn oxygen_generator_rating(&self) -> BitString {
+        self.filter_by(criteria_most_common)
+    }
+
+    fn co2_scrubber_rating(&self) -> BitString {
+        self.filter_by(criteria_least_common)
+    }
+}
+
+type Criteria = fn(usize, usize) -> bool;
+
+fn criteria_most_common(zeros: usize, ones: usize) -> bool {
+    ones >= zeros
+}
+
+fn criteria_least_common(zeros: usize, ones: usize) -> bool {
+    !criteria_most_common(zeros, ones)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEBUG_DATA: &str = "00100\n\
+                              11110\n\
+                              10110\n\
+                              10111\n\
+                              10101\n\
+                              01111\n\
+                              00111\n\
+                              11100\n\
+                              10000\n\
+                              11001\n\
+                              00010\n\
+                              01010\n";
+
+    #[test]
+    fn test_bit_string() -> Result<()> {
+        let bits = BitString::from_bits("10110".chars().map(|c| c == '1'));
+        assert_eq!(bits.to_string(), "10110");
+        assert_eq!(bits.to_u64()?, 22);
+
+        let wide = format!("{}1{}", "0".repeat(70), "1".repeat(63));
+        let bits = BitString::from_bits(wide.chars().map(|c| c == '1'));
+        assert_eq!(bits.to_string(), wide);
+        assert_eq!(bits.to_u64()?, u64::MAX);
+        assert!(BitString::from_bits(wide.chars().rev().map(|c| c == '1'))
+            .to_u64()
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_part1() -> Result<()> {
+        let diag = Diagnostics::from(DEBUG_DATA)?;
+        assert_eq!(diag.width, 5);
+        assert_eq!(diag.gamma().to_u64()?, 22);
+        assert_eq!(diag.epsilon().to_u64()?, 9);
+        assert_eq!(Day03::part1(&diag)?, 198);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> Result<()> {
+        let diag = Diagnostics::from(DEBUG_DATA)?;
+        let trie = diag.trie();
+        assert_eq!(trie.oxygen_generator_rating().to_u64()?, 23);
+        assert_eq!(trie.co2_scrubber_rating().to_u64()?, 10);
+        assert_eq!(Day03::part2(&diag)?, 230);
+        Ok(())
+    }
+
+    #[test]
+    fn test_wide() -> Result<()> {
+        // 130 bits per line: the debug report followed by a constant block of 125 bits
+        let suffix: String = (0..125)
+            .map(|i| if i % 3 == 0 { '1' } else { '0' })
+            .collect();
+        let report: String = DEBUG_DATA
+            .lines()
+            .map(|l| format!("{}{}\n", l, suffix))
+            .collect();
+        let diag = Diagnostics::from(&report)?;
+        assert_eq!(diag.width, 130);
+        assert_eq!(diag.gamma().to_string(), format!("10110{}", suffix));
+
+        let trie = diag.trie();
+        assert_eq!(
+            trie.oxygen_generator_rating().to_string(),
+            format!("10111{}", suffix)
+        );
+        assert_eq!(
+            trie.co2_scrubber_rating().to_string(),
+            format!("01010{}", suffix)
+        );
+        assert!(Day03::part2(&diag).is_err());
+
+        assert!(Diagnostics::from("0101\n011\n").is_err());
+        assert!(Diagnostics::from("0121\n").is_err());
+        Ok(())
+    }
+}
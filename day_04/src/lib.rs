@@ -0,0 +1,292 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{num1, parse_all, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, one_of, space0, space1,
+    },
+    combinator::{map, map_res, value, verify},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Bingo;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        Bingo::from(i)
+    }
+
+    fn part1(bingo: &Self::Input) -> Result<u64> {
+        Ok(bingo
+            .timeline()
+            .first()
+            .context("There was no bingo!")?
+            .score)
+    }
+
+    fn part2(bingo: &Self::Input) -> Result<u64> {
+        Ok(bingo
+            .timeline()
+            .last()
+            .context("There was no bingo!")?
+            .score)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bingo {
+    draws: Vec<u64>,
+    cards: Vec<Card>,
+    /// Whether completing one of the two diagonals also counts as bingo.
+    diagonals: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Card {
+    val_to_num: HashMap<u64, Number>,
+
+    col_to_bingo: Vec<usize>,
+    row_to_bingo: Vec<usize>,
+    // main diagonal and anti-diagonal
+    diag_to_bingo: [usize; 2],
+
+    has_bingo: bool,
+}
+
+#[derive(Debug, Hash, Clone)]
+struct Number {
+    col: usize,
+    row: usize,
+    marked: bool,
+}
+
+/// A card winning the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Win {
+    /// Index of the draw completing the card.
+    pub draw: usize,
+    /// Index of the card in the input.
+    pub card: usize,
+    pub score: u64,
+}
+
+impl Parseable for Bingo {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let (i, draws) = terminated(separated_list1(char(','), num1), many1(line_ending))(i)?;
+        let (i, cards) = separated_list1(pair(many1(line_ending), space0), Card::parse)(i)?;
+
+        Ok((
+            i,
+            Self {
+                draws,
+                cards,
+                diagonals: false,
+            },
+        ))
+    }
+}
+
+impl Bingo {
+    fn from(i: &str) -> Result<Self> {
+        parse_all(i)
+    }
+
+    pub fn with_diagonals(mut self, diagonals: bool) -> Self {
+        self.diagonals = diagonals;
+        self
+    }
+
+    /// Play the whole game and return all cards in the order they win.
+    ///
+    /// Cards winning on the same draw are ordered by their index.
+    pub fn timeline(&self) -> Vec<Win> {
+        let mut cards = self.cards.clone();
+        let mut timeline = Vec::with_capacity(cards.len());
+        for (draw, drawn) in self.draws.iter().enumerate() {
+            for (idx, card) in cards.iter_mut().enumerate().filter(|(_, c)| !c.has_bingo()) {
+                if card.check(*drawn, self.diagonals) {
+                    timeline.push(Win {
+                        draw,
+                        card: idx,
+                        score: drawn * card.score(),
+                    });
+                }
+            }
+            if timeline.len() == cards.len() {
+                break;
+            }
+        }
+        timeline
+    }
+
+    /// The `k`-th (0-based) card to win, if at least `k + 1` cards win.
+    pub fn kth_winner(&self, k: usize) -> Option<Win> {
+        self.timeline().get(k).copied()
+    }
+}
+
+impl Parseable for Card {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let (i, lines) = verify(
+            terminated(
+                separated_list1(pair(line_ending, space0), numline),
+                line_ending,
+            ),
+            |lines: &Vec<Vec<u64>>| lines.iter().all(|line| line.len() == lines.len()),
+        )(i)?;
+        let num_rows = lines.len();
+
+        let mut val_to_num = HashMap::new();
+
+        for (row, line) in lines.into_iter().enumerate() {
+            for (col, num) in line.into_iter().enumerate() {
+                val_to_num.insert(
+                    num,
+                    Number {
+                        col,
+                        row,
+                        marked: false,
+                    },
+                );
+            }
+        }
+        Ok((
+            i,
+            Self {
+                val_to_num,
+                col_to_bingo: vec![0; num_rows],
+                row_to_bingo: vec![0; num_rows],
+                diag_to_bingo: [0; 2],
+                has_bingo: false,
+            },
+        ))
+    }
+}
+
+impl Card {
+    // returns if card has bingo
+    fn check(&mut self, num: u64, diagonals: bool) -> bool {
+        let needed = self.needed_for_bingo();
+        if let Entry::Occupied(num) = self.val_to_num.entry(num) {
+            let num = num.into_mut();
+            if !num.marked {
+                num.marked = true;
+                self.col_to_bingo[num.col] += 1;
+                self.row_to_bingo[num.row] += 1;
+                if self.col_to_bingo[num.col] == needed || self.row_to_bingo[num.row] == needed {
+                    self.has_bingo = true;
+                }
+                if num.row == num.col {
+                    self.diag_to_bingo[0] += 1;
+                }
+                if num.row + num.col + 1 == needed {
+                    self.diag_to_bingo[1] += 1;
+                }
+                if diagonals && self.diag_to_bingo.contains(&needed) {
+                    self.has_bingo = true;
+                }
+            }
+        }
+        self.has_bingo
+    }
+
+    fn has_bingo(&self) -> bool {
+        self.has_bingo
+    }
+
+    fn score(&self) -> u64 {
+        self.val_to_num
+            .iter()
+            .filter_map(|(val, num)| if !num.marked { Some(val) } else { None })
+            .sum()
+    }
+
+    fn needed_for_bingo(&self) -> usize {
+        self.row_to_bingo.len()
+    }
+}
+
+fn numline(i: &str) -> IResult<&str, Vec<u64>> {
+    separated_list1(space1, num1)(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::ParseError;
+
+    #[test]
+    fn test_part1() -> Result<()> {
+        let bingo = Bingo::from(&read_to_string(PathBuf::from("debug.txt"))?)?;
+        assert_eq!(bingo.cards.len(), 3, "did not parse all cards");
+        for card in bingo.cards.iter() {
+            assert_eq!(card.col_to_bingo.len(), 5, "cards have wrong dimensions");
+        }
+
+        assert_eq!(Day04::part1(&bingo)?, 4512);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> Result<()> {
+        let bingo = Bingo::from(&read_to_string(PathBuf::from("debug.txt"))?)?;
+        assert_eq!(Day04::part2(&bingo)?, 1924);
+        Ok(())
+    }
+
+    #[test]
+    fn test_timeline() -> Result<()> {
+        let bingo = Bingo::from(&read_to_string(PathBuf::from("debug.txt"))?)?;
+        let win = |draw, card, score| Win { draw, card, score };
+        assert_eq!(
+            bingo.timeline(),
+            vec![win(11, 2, 4512), win(13, 0, 2192), win(14, 1, 1924)]
+        );
+        assert_eq!(bingo.kth_winner(1), Some(win(13, 0, 2192)));
+        assert_eq!(bingo.kth_winner(3), None);
+
+        let bingo = bingo.with_diagonals(true);
+        assert_eq!(bingo.kth_winner(0), Some(win(7, 2, 494)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_small_cards() -> Result<()> {
+        let bingo = Bingo::from("5,1,9,3,7\n\n1 2 3\n4 5 6\n7 8 9\n\n9 8\n7 6\n")?;
+        assert_eq!(bingo.cards[0].col_to_bingo.len(), 3);
+        assert_eq!(bingo.cards[1].col_to_bingo.len(), 2);
+
+        // the 3x3 card only ever completes diagonals
+        let win = |draw, card, score| Win { draw, card, score };
+        assert_eq!(bingo.timeline(), vec![win(4, 1, 7 * 14)]);
+        assert_eq!(
+            bingo.with_diagonals(true).timeline(),
+            vec![win(2, 0, 9 * 30), win(4, 1, 7 * 14)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_card_not_square() {
+        let err = Bingo::from("1,2\n\n1 2\n3 4\n5 6\n").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (3, 1));
+    }
+}
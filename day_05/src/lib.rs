@@ -0,0 +1,397 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{num1, parse_all_with, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, one_of, space0, space1,
+    },
+    combinator::{map, map_res, value, verify},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = VentLines;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        VentLine::read_lines(i)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<usize> {
+        Ok(count_overlaps(lines, false, Method::default()))
+    }
+
+    fn part2(lines: &Self::Input) -> Result<usize> {
+        Ok(count_overlaps(lines, true, Method::default()))
+    }
+}
+
+pub struct VentLine {
+    from: (i64, i64),
+    to: (i64, i64),
+}
+
+impl Parseable for VentLine {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        verify(
+            map(separated_pair(point, tag(" -> "), point), |(from, to)| {
+                Self { from, to }
+            }),
+            |line: &Self| line.is_straight() || line.is_diagonal(),
+        )(i)
+    }
+}
+
+pub type VentLines = Vec<VentLine>;
+
+fn vent_lines(i: &str) -> IResult<&str, VentLines> {
+    separated_list1(line_ending, VentLine::parse)(i)
+}
+
+impl VentLine {
+    fn read_lines(i: &str) -> Result<VentLines> {
+        parse_all_with(vent_lines, i)
+    }
+
+    fn is_vertical(&self) -> bool {
+        self.from.0 == self.to.0
+    }
+
+    fn is_horizontal(&self) -> bool {
+        self.from.1 == self.to.1
+    }
+
+    fn is_straight(&self) -> bool {
+        self.is_horizontal() || self.is_vertical()
+    }
+
+    fn is_diagonal(&self) -> bool {
+        (self.to.0 - self.from.0).abs() == (self.to.1 - self.from.1).abs()
+    }
+
+    fn get_direction(&self) -> (i64, i64) {
+        (
+            ordering_to_direction(self.to.0.cmp(&self.from.0)),
+            ordering_to_direction(self.to.1.cmp(&self.from.1)),
+        )
+    }
+
+    fn get_points(&self) -> Vec<(i64, i64)> {
+        let dir = self.get_direction();
+        let mut current = self.from;
+
+        let mut points = vec![current];
+
+        while current != self.to {
+            current = (current.0 + dir.0, current.1 + dir.1);
+            points.push(current);
+        }
+        points
+    }
+}
+
+/// How to count the points covered by at least two vent lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Method {
+    /// Enumerate all points of all lines, memory grows with the length of the lines.
+    #[default]
+    Points,
+    /// Intersect the lines with each other, memory only grows with the number of lines.
+    Sweep,
+}
+
+impl FromStr for Method {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "points" => Ok(Method::Points),
+            "sweep" => Ok(Method::Sweep),
+            _ => bail!("Invalid method: {} (must be points or sweep)", s),
+        }
+    }
+}
+
+/// Count points covered by at least two lines, diagonal lines are ignored unless `diagonals` is set.
+pub fn count_overlaps(lines: &[VentLine], diagonals: bool, method: Method) -> usize {
+    let lines: Vec<_> = lines
+        .iter()
+        .filter(|l| diagonals || l.is_straight())
+        .collect();
+    match method {
+        Method::Points => count_overlaps_points(&lines),
+        Method::Sweep => count_overlaps_sweep(&lines),
+    }
+}
+
+fn count_overlaps_points(lines: &[&VentLine]) -> usize {
+    let mut num_overlaps: HashMap<(i64, i64), usize> = HashMap::new();
+
+    for line in lines.iter() {
+        for point in line.get_points().into_iter() {
+            num_overlaps
+                .entry(point)
+                .and_modify(|c| {
+                    *c += 1;
+                })
+                .or_insert(1);
+        }
+    }
+
+    num_overlaps.values().filter(|v| **v > 1).count()
+}
+
+/// Infinite line through a vent line, identified by its (normalized) direction and the value of
+/// `dy * x - dx * y` that all points on it share.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+struct Carrier {
+    dir: (i64, i64),
+    offset: i64,
+}
+
+impl Carrier {
+    fn through(point: (i64, i64), dir: (i64, i64)) -> Self {
+        Self {
+            dir,
+            offset: dir.1 * point.0 - dir.0 * point.1,
+        }
+    }
+
+    /// Coordinate of `point` along the carrier.
+    fn coord(&self, point: (i64, i64)) -> i64 {
+        if self.dir.0 != 0 {
+            point.0
+        } else {
+            point.1
+        }
+    }
+
+    /// Lattice point where both carriers cross, if any.
+    fn intersect(&self, other: &Self) -> Option<(i64, i64)> {
+        let (d1, d2) = (self.dir, other.dir);
+        let det = d1.0 * d2.1 - d1.1 * d2.0;
+        if det == 0 {
+            return None;
+        }
+        let x = d1.0 * other.offset - d2.0 * self.offset;
+        let y = d1.1 * other.offset - d2.1 * self.offset;
+        // diagonals of different orientation can cross between lattice points
+        if x % det != 0 || y % det != 0 {
+            return None;
+        }
+        Some((x / det, y / det))
+    }
+}
+
+/// Vent line as a closed interval on its carrier.
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    carrier: Carrier,
+    min: (i64, i64),
+    max: (i64, i64),
+}
+
+impl Segment {
+    fn new(line: &VentLine) -> Self {
+        let dir = match line.get_direction() {
+            (0, 0) => (1, 0),
+            (dx, dy) if dx < 0 || (dx == 0 && dy < 0) => (-dx, -dy),
+            dir => dir,
+        };
+        Self {
+            carrier: Carrier::through(line.from, dir),
+            min: (line.from.0.min(line.to.0), line.from.1.min(line.to.1)),
+            max: (line.from.0.max(line.to.0), line.from.1.max(line.to.1)),
+        }
+    }
+
+    fn contains(&self, point: (i64, i64)) -> bool {
+        (self.min.0..=self.max.0).contains(&point.0) && (self.min.1..=self.max.1).contains(&point.1)
+    }
+
+    /// Range of coordinates along the carrier.
+    fn range(&self) -> (i64, i64) {
+        let (a, b) = (self.carrier.coord(self.min), self.carrier.coord(self.max));
+        (a.min(b), a.max(b))
+    }
+}
+
+/// Count overlaps without enumerating points.
+///
+/// Collinear lines overlap in intervals, found by sweeping over the interval bounds of each
+/// carrier. All other overlaps are single crossing points, found by sweeping over the lines
+/// sorted by their smallest x-coordinate and intersecting those whose x-ranges overlap. Crossing
+/// points that lie in an overlap interval must only be counted once.
+fn count_overlaps_sweep(lines: &[&VentLine]) -> usize {
+    let mut segments: Vec<_> = lines.iter().map(|l| Segment::new(l)).collect();
+
+    let mut by_carrier: HashMap<Carrier, Vec<(i64, i64)>> = HashMap::new();
+    for segment in segments.iter() {
+        by_carrier
+            .entry(segment.carrier)
+            .or_default()
+            .push(segment.range());
+    }
+    let overlaps: HashMap<Carrier, Vec<(i64, i64)>> = by_carrier
+        .into_iter()
+        .map(|(carrier, ranges)| (carrier, overlapping_ranges(ranges)))
+        .filter(|(_, overlaps)| !overlaps.is_empty())
+        .collect();
+    let num_in_overlaps: usize = overlaps
+        .values()
+        .flatten()
+        .map(|(lo, hi)| (hi - lo + 1) as usize)
+        .sum();
+
+    segments.sort_by_key(|s| s.min.0);
+    let mut crossings = HashSet::new();
+    for (idx, first) in segments.iter().enumerate() {
+        for second in segments[idx + 1..]
+            .iter()
+            .take_while(|s| s.min.0 <= first.max.0)
+        {
+            if let Some(point) = first.carrier.intersect(&second.carrier) {
+                if first.contains(point) && second.contains(point) {
+                    crossings.insert(point);
+                }
+            }
+        }
+    }
+
+    let crossings_in_overlaps: usize = crossings
+        .iter()
+        .map(|point| {
+            [(1, 0), (0, 1), (1, 1), (1, -1)]
+                .into_iter()
+                .filter(|dir| {
+                    let carrier = Carrier::through(*point, *dir);
+                    let coord = carrier.coord(*point);
+                    overlaps.get(&carrier).is_some_and(|ranges| {
+                        let idx = ranges.partition_point(|(_, hi)| *hi < coord);
+                        ranges.get(idx).is_some_and(|(lo, _)| *lo <= coord)
+                    })
+                })
+                .count()
+        })
+        .sum();
+
+    num_in_overlaps + crossings.len() - crossings_in_overlaps
+}
+
+/// Sorted, disjoint ranges covered by at least two of the given (inclusive) ranges.
+fn overlapping_ranges(ranges: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    let mut events: Vec<_> = ranges
+        .into_iter()
+        .flat_map(|(lo, hi)| [(lo, 1), (hi + 1, -1)])
+        .collect();
+    events.sort_unstable();
+
+    let mut overlaps = Vec::new();
+    let mut coverage = 0;
+    let mut start = None;
+    for (coord, change) in events {
+        coverage += change;
+        match start {
+            None if coverage > 1 => start = Some(coord),
+            Some(lo) if coverage < 2 => {
+                overlaps.push((lo, coord - 1));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    overlaps
+}
+
+fn ordering_to_direction(ord: Ordering) -> i64 {
+    match ord {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+fn point(i: &str) -> IResult<&str, (i64, i64)> {
+    separated_pair(num1, char(','), num1)(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Lcg;
+
+    #[test]
+    fn test_part1() {
+        let content = read_to_string(PathBuf::from("debug.txt")).unwrap();
+        let lines = VentLine::read_lines(&content).unwrap();
+        assert_eq!(lines.len(), 10, "Did not read all lines");
+        assert_eq!(count_overlaps(&lines[..], false, Method::Points), 5);
+        assert_eq!(count_overlaps(&lines[..], false, Method::Sweep), 5);
+    }
+
+    #[test]
+    fn test_part2() {
+        let content = read_to_string(PathBuf::from("debug.txt")).unwrap();
+        let lines = VentLine::read_lines(&content).unwrap();
+        assert_eq!(lines.len(), 10, "Did not read all lines");
+        assert_eq!(count_overlaps(&lines[..], true, Method::Points), 12);
+        assert_eq!(count_overlaps(&lines[..], true, Method::Sweep), 12);
+    }
+
+    #[test]
+    fn test_sweep_matches_points() {
+        // deterministic pseudo-random lines in a small area to provoke many overlaps
+        let mut rng = Lcg::new(0x2021_0005);
+        let mut next = |max: i64| rng.below(max as u64) as i64;
+        for _ in 0..20 {
+            let lines: Vec<_> = (0..60)
+                .map(|_| {
+                    let from = (next(20), next(20));
+                    let len = next(12);
+                    let dir = [(1, 0), (0, 1), (1, 1), (1, -1), (-1, 1)][next(5) as usize];
+                    let to = (from.0 + dir.0 * len, from.1 + dir.1 * len);
+                    VentLine { from, to }
+                })
+                .collect();
+            for diagonals in [false, true] {
+                assert_eq!(
+                    count_overlaps(&lines, diagonals, Method::Sweep),
+                    count_overlaps(&lines, diagonals, Method::Points),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_sweep_large() -> Result<()> {
+        let lines = VentLine::read_lines(
+            "0,0 -> 1000000000000,0\n\
+             500000000000,0 -> 2000000000000,0\n\
+             0,1000000000000 -> 1000000000000,0\n\
+             750000000000,300000000000 -> 750000000000,0\n",
+        )?;
+        assert_eq!(count_overlaps(&lines, false, Method::Sweep), 500000000001);
+        assert_eq!(count_overlaps(&lines, true, Method::Sweep), 500000000002);
+
+        assert!(VentLine::read_lines("0,0 -> 2,1\n").is_err());
+        Ok(())
+    }
+}
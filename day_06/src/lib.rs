@@ -0,0 +1,300 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{comma_list, num1, parse_all_with, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, one_of, space0, space1,
+    },
+    combinator::{map, map_res, value},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use num_bigint::BigUint;
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = Vec<u64>;
+    type Answer1 = usize;
+    type Answer2 = BigUint;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        read_jellyfish(i)
+    }
+
+    fn part1(jellyfish: &Self::Input) -> Result<usize> {
+        Ok(count_jellyfish(jellyfish.clone(), 80, LifeCycle::default()))
+    }
+
+    fn part2(jellyfish: &Self::Input) -> Result<BigUint> {
+        LifeCycle::default().count_big(jellyfish, 256)
+    }
+}
+
+/// Timers of the lanternfish: after spawning, a fish's timer is set to `reset`, the timer of
+/// the newborn fish starts at `newborn`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifeCycle {
+    pub reset: usize,
+    pub newborn: usize,
+}
+
+impl Default for LifeCycle {
+    fn default() -> Self {
+        Self {
+            reset: 6,
+            newborn: 8,
+        }
+    }
+}
+
+impl LifeCycle {
+    fn num_states(&self) -> usize {
+        self.reset.max(self.newborn) + 1
+    }
+
+    /// Count fish per timer value.
+    fn inventory(&self, jellyfish: &[u64]) -> Result<Vec<usize>> {
+        let mut counts = vec![0; self.num_states()];
+        for jf in jellyfish {
+            match counts.get_mut(*jf as usize) {
+                Some(count) => *count += 1,
+                None => bail!("Timer {} exceeds the life cycle {:?}", jf, self),
+            }
+        }
+        Ok(counts)
+    }
+
+    /// Number of fish after `rounds`, computed round by round.
+    ///
+    /// Fails once the count no longer fits into a `usize`, use `count_big` for that many fish.
+    pub fn count(&self, jellyfish: &[u64], rounds: usize) -> Result<usize> {
+        let overflow = || format!("More than {} fish, use count_big instead", usize::MAX);
+        let mut state_to_count = self.inventory(jellyfish)?;
+        for _ in 0..rounds {
+            let spawning = state_to_count[0];
+            state_to_count.rotate_left(1);
+            // rotating moved the spawning fish to the last state
+            *state_to_count.last_mut().unwrap() -= spawning;
+            for state in [self.newborn, self.reset] {
+                state_to_count[state] = state_to_count[state]
+                    .checked_add(spawning)
+                    .with_context(overflow)?;
+            }
+        }
+        state_to_count
+            .iter()
+            .try_fold(0usize, |sum, count| sum.checked_add(*count))
+            .with_context(overflow)
+    }
+
+    /// Number of fish after `rounds`, computed in O(log(rounds)) steps by exponentiating the
+    /// transition matrix of a single round.
+    pub fn count_big(&self, jellyfish: &[u64], rounds: u64) -> Result<BigUint> {
+        let mut counts: Vec<BigUint> = self
+            .inventory(jellyfish)?
+            .into_iter()
+            .map(BigUint::from)
+            .collect();
+
+        let mut transition = self.transition();
+        let mut rounds = rounds;
+        while rounds > 0 {
+            if rounds & 1 == 1 {
+                counts = transition.apply(&counts);
+            }
+            rounds >>= 1;
+            if rounds > 0 {
+                transition = transition.square();
+            }
+        }
+        Ok(counts.into_iter().sum())
+    }
+
+    /// Matrix mapping the counts per timer value onto those of the next round.
+    fn transition(&self) -> Matrix {
+        let size = self.num_states();
+        let mut matrix = Matrix::zero(size);
+        for state in 1..size {
+            matrix.set(state - 1, state, 1u8);
+        }
+        matrix.set(self.newborn, 0, 1u8);
+        *matrix.get_mut(self.reset, 0) += 1u8;
+        matrix
+    }
+}
+
+/// Square matrix of big integers, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Matrix {
+    size: usize,
+    entries: Vec<BigUint>,
+}
+
+impl Matrix {
+    fn zero(size: usize) -> Self {
+        Self {
+            size,
+            entries: vec![BigUint::default(); size * size],
+        }
+    }
+
+    fn get(&self, row: usize, col: usize) -> &BigUint {
+        &self.entries[row * self.size + col]
+    }
+
+    fn get_mut(&mut self, row: usize, col: usize) -> &mut BigUint {
+        &mut self.entries[row * self.size + col]
+    }
+
+    fn set<T: Into<BigUint>>(&mut self, row: usize, col: usize, value: T) {
+        *self.get_mut(row, col) = value.into();
+    }
+
+    fn square(&self) -> Self {
+        let mut squared = Self::zero(self.size);
+        for row in 0..self.size {
+            for k in 0..self.size {
+                let lhs = self.get(row, k);
+                if *lhs == BigUint::default() {
+                    continue;
+                }
+                for col in 0..self.size {
+                    *squared.get_mut(row, col) += lhs * self.get(k, col);
+                }
+            }
+        }
+        squared
+    }
+
+    fn apply(&self, vector: &[BigUint]) -> Vec<BigUint> {
+        (0..self.size)
+            .map(|row| {
+                (0..self.size)
+                    .map(|col| self.get(row, col) * &vector[col])
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+/// Simulate every single fish, only feasible for few rounds.
+fn count_jellyfish(mut jellyfish: Vec<u64>, rounds: usize, cycle: LifeCycle) -> usize {
+    for _ in 0..rounds {
+        let mut num_additions = 0;
+        for jf in jellyfish.iter_mut() {
+            if *jf == 0 {
+                *jf = cycle.reset as u64;
+                num_additions += 1;
+            } else {
+                *jf -= 1;
+            }
+        }
+        jellyfish.resize(jellyfish.len() + num_additions, cycle.newborn as u64);
+    }
+    jellyfish.len()
+}
+
+fn read_jellyfish(i: &str) -> Result<Vec<u64>> {
+    parse_all_with(comma_list(num1), i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() -> Result<()> {
+        let content = read_to_string(PathBuf::from("debug.txt"))?;
+        let jellyfish = read_jellyfish(&content)?;
+        let cycle = LifeCycle::default();
+        assert_eq!(count_jellyfish(jellyfish.clone(), 18, cycle), 26);
+        assert_eq!(count_jellyfish(jellyfish.clone(), 80, cycle), 5934);
+        assert_eq!(cycle.count(&jellyfish, 80)?, 5934);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> Result<()> {
+        let content = read_to_string(PathBuf::from("debug.txt"))?;
+        let jellyfish = read_jellyfish(&content)?;
+        let cycle = LifeCycle::default();
+        assert_eq!(cycle.count(&jellyfish, 256)?, 26984457539);
+        assert_eq!(
+            cycle.count_big(&jellyfish, 256)?,
+            BigUint::from(26984457539u64)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_life_cycles() -> Result<()> {
+        let jellyfish = read_jellyfish("3,4,3,1,2\n")?;
+        for (reset, newborn) in [(6, 8), (2, 4), (5, 3), (4, 4), (0, 4)] {
+            let cycle = LifeCycle { reset, newborn };
+            for rounds in [0, 1, 7, 20] {
+                let expected = count_jellyfish(jellyfish.clone(), rounds, cycle);
+                assert_eq!(cycle.count(&jellyfish, rounds)?, expected, "{:?}", cycle);
+                assert_eq!(
+                    cycle.count_big(&jellyfish, rounds as u64)?,
+                    BigUint::from(expected),
+                    "{:?}",
+                    cycle
+                );
+            }
+        }
+        assert!(LifeCycle {
+            reset: 1,
+            newborn: 2
+        }
+        .count(&jellyfish, 1)
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_many_rounds() -> Result<()> {
+        let jellyfish = read_jellyfish("3,4,3,1,2\n")?;
+        let cycle = LifeCycle::default();
+        let err = cycle.count(&jellyfish, 1000).unwrap_err();
+        assert!(err.to_string().contains("count_big"), "{}", err);
+
+        // u64 overflows after a few hundred rounds, compare against rounds computed one by one
+        let mut counts: Vec<BigUint> = cycle
+            .inventory(&jellyfish)?
+            .into_iter()
+            .map(BigUint::from)
+            .collect();
+        for _ in 0..5000 {
+            counts.rotate_left(1);
+            let spawning = counts[8].clone();
+            counts[6] += spawning;
+        }
+        let expected: BigUint = counts.into_iter().sum();
+        assert_eq!(cycle.count_big(&jellyfish, 5000)?, expected);
+
+        // every fish spawns each round, doubling the population
+        let reset_only = LifeCycle {
+            reset: 0,
+            newborn: 0,
+        };
+        assert_eq!(
+            reset_only.count_big(&[0], 1 << 16)?,
+            BigUint::from(1u8) << (1usize << 16)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,288 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{comma_list, num1, parse_all_with, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, one_of, space0, space1,
+    },
+    combinator::{map, map_res, value},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Crabs;
+    type Answer1 = i128;
+    type Answer2 = i128;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        read(i)
+    }
+
+    fn part1(crabs: &Self::Input) -> Result<i128> {
+        Ok(align(crabs, &Linear).1)
+    }
+
+    fn part2(crabs: &Self::Input) -> Result<i128> {
+        Ok(align(crabs, &Triangular).1)
+    }
+}
+
+fn read(i: &str) -> Result<Crabs> {
+    Crabs::new(parse_all_with(comma_list(num1), i)?)
+}
+
+/// Sorted crab positions along with their prefix sums.
+///
+/// Sums are kept as `i128`, so they cannot overflow for any `i64` positions.
+#[derive(Debug, Clone)]
+pub struct Crabs {
+    positions: Vec<i64>,
+    // prefix[i] is the sum of the first i positions
+    prefix: Vec<i128>,
+    sum_squares: i128,
+}
+
+impl Crabs {
+    /// Fails if there are no crabs, as there is nothing to align then.
+    pub fn new(mut positions: Vec<i64>) -> Result<Self> {
+        if positions.is_empty() {
+            bail!("There are no crabs.");
+        }
+        positions.sort_unstable();
+        let mut prefix = Vec::with_capacity(positions.len() + 1);
+        prefix.push(0);
+        for p in positions.iter() {
+            prefix.push(prefix.last().unwrap() + *p as i128);
+        }
+        let sum_squares = positions.iter().map(|p| (*p as i128).pow(2)).sum();
+        Ok(Self {
+            positions,
+            prefix,
+            sum_squares,
+        })
+    }
+
+    fn len(&self) -> i128 {
+        self.positions.len() as i128
+    }
+
+    fn sum(&self) -> i128 {
+        *self.prefix.last().unwrap()
+    }
+
+    fn min(&self) -> i64 {
+        self.positions[0]
+    }
+
+    fn max(&self) -> i64 {
+        *self.positions.last().unwrap()
+    }
+
+    /// Sum of the distances of all crabs to `target` in O(log n).
+    fn sum_distances(&self, target: i64) -> i128 {
+        let below = self.positions.partition_point(|p| *p < target);
+        let (num_below, num_above) = (below as i128, self.len() - below as i128);
+        let target = target as i128;
+        (target * num_below - self.prefix[below])
+            + (self.sum() - self.prefix[below] - target * num_above)
+    }
+
+    /// Sum of the squared distances of all crabs to `target` in O(1).
+    fn sum_squared_distances(&self, target: i64) -> i128 {
+        let target = target as i128;
+        self.sum_squares - 2 * target * self.sum() + self.len() * target * target
+    }
+}
+
+/// Fuel needed by a crab to move a given distance.
+///
+/// The cost has to be convex in the distance (i.e. each additional step costs at least as much as
+/// the previous one) so that the total fuel has a single minimum.
+pub trait CostModel {
+    fn cost(&self, distance: i64) -> i128;
+
+    /// Fuel needed by all crabs to align at `target`.
+    fn total(&self, crabs: &Crabs, target: i64) -> i128 {
+        crabs
+            .positions
+            .iter()
+            .map(|p| self.cost((p - target).abs()))
+            .sum()
+    }
+
+    /// Range of targets that contains an optimal one.
+    fn bounds(&self, crabs: &Crabs) -> (i64, i64) {
+        (crabs.min(), crabs.max())
+    }
+}
+
+/// Every step costs one unit of fuel.
+pub struct Linear;
+
+impl CostModel for Linear {
+    fn cost(&self, distance: i64) -> i128 {
+        distance.into()
+    }
+
+    fn total(&self, crabs: &Crabs, target: i64) -> i128 {
+        crabs.sum_distances(target)
+    }
+
+    fn bounds(&self, crabs: &Crabs) -> (i64, i64) {
+        // any median is optimal
+        let median = crabs.positions[crabs.positions.len() / 2];
+        (median, median)
+    }
+}
+
+/// Each step costs one unit more than the previous one.
+pub struct Triangular;
+
+impl CostModel for Triangular {
+    fn cost(&self, distance: i64) -> i128 {
+        let distance = distance as i128;
+        distance * (distance + 1) / 2
+    }
+
+    fn total(&self, crabs: &Crabs, target: i64) -> i128 {
+        (crabs.sum_squared_distances(target) + crabs.sum_distances(target)) / 2
+    }
+
+    fn bounds(&self, crabs: &Crabs) -> (i64, i64) {
+        // the real-valued optimum is within 1/2 of the mean
+        let mean = crabs.sum().div_euclid(crabs.len()) as i64;
+        ((mean - 1).max(crabs.min()), (mean + 2).min(crabs.max()))
+    }
+}
+
+/// User-supplied convex cost, the total fuel is computed crab by crab.
+pub struct Convex<F: Fn(i64) -> i64>(pub F);
+
+impl<F: Fn(i64) -> i64> CostModel for Convex<F> {
+    fn cost(&self, distance: i64) -> i128 {
+        (self.0)(distance).into()
+    }
+}
+
+/// Find the target position needing the least fuel, returns target and fuel.
+///
+/// The total fuel is convex in the target, so the optimum is found by a binary search for the
+/// first target where moving one further does not reduce the fuel.
+pub fn align<M: CostModel>(crabs: &Crabs, model: &M) -> (i64, i128) {
+    let (mut lo, mut hi) = model.bounds(crabs);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if model.total(crabs, mid + 1) < model.total(crabs, mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    (lo, model.total(crabs, lo))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Lcg;
+
+    /// Try every target position between the outermost crabs.
+    fn align_brute_force<M: CostModel>(crabs: &Crabs, model: &M) -> i128 {
+        (crabs.min()..=crabs.max())
+            .map(|target| {
+                crabs
+                    .positions
+                    .iter()
+                    .map(|p| model.cost((p - target).abs()))
+                    .sum()
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_part1() -> Result<()> {
+        let crabs = read(&read_to_string(PathBuf::from("debug.txt"))?)?;
+        assert_eq!(align(&crabs, &Linear), (2, 37));
+        assert_eq!(align_brute_force(&crabs, &Linear), 37);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> Result<()> {
+        let crabs = read(&read_to_string(PathBuf::from("debug.txt"))?)?;
+        assert_eq!(align(&crabs, &Triangular), (5, 168));
+        assert_eq!(align_brute_force(&crabs, &Triangular), 168);
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        // deterministic pseudo-random crabs
+        let mut rng = Lcg::new(0x2021_0007);
+        let mut next = |max: u64| rng.below(max) as i64;
+        for round in 0..50 {
+            let len = 1 + next(30) as usize;
+            let spread = 1 + (round as u64) * 20;
+            let crabs = Crabs::new((0..len).map(|_| next(spread) - 100).collect()).unwrap();
+
+            assert_eq!(align(&crabs, &Linear).1, align_brute_force(&crabs, &Linear));
+            assert_eq!(
+                align(&crabs, &Triangular).1,
+                align_brute_force(&crabs, &Triangular)
+            );
+            let squared = Convex(|d| d * d);
+            assert_eq!(
+                align(&crabs, &squared).1,
+                align_brute_force(&crabs, &squared)
+            );
+            let free_steps = Convex(|d| (d - 5).max(0));
+            assert_eq!(
+                align(&crabs, &free_steps).1,
+                align_brute_force(&crabs, &free_steps)
+            );
+        }
+    }
+
+    #[test]
+    fn test_huge_range() -> Result<()> {
+        let crabs = Crabs::new(vec![-1_000_000_000, 3, 7, 1_000_000_000, 999_999_999])?;
+        assert_eq!(align(&crabs, &Linear), (7, 2_999_999_996));
+        let (target, fuel) = align(&crabs, &Triangular);
+        assert_eq!(target, 200_000_002);
+        assert_eq!(fuel, Triangular.total(&crabs, target));
+        assert!(Triangular.total(&crabs, target - 1) > fuel);
+        assert!(Triangular.total(&crabs, target + 1) > fuel);
+        assert_eq!(align(&crabs, &Convex(|d| d * d)).0, 200_000_002);
+
+        // the total fuel no longer fits into an i64
+        let crabs = Crabs::new(vec![
+            -4_000_000_000_000_000_000,
+            0,
+            4_000_000_000_000_000_000,
+        ])?;
+        assert_eq!(align(&crabs, &Linear), (0, 8_000_000_000_000_000_000));
+        let (target, fuel) = align(&crabs, &Triangular);
+        assert_eq!(target, 0);
+        assert_eq!(fuel, 16_000_000_000_000_000_004_000_000_000_000_000_000);
+
+        assert!(Crabs::new(vec![]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,500 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{parse_all, parse_all_with, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, one_of, space0, space1,
+    },
+    combinator::{map, map_res, value, verify},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = Vec<Configuration>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        read(i)
+    }
+
+    fn part1(configs: &Self::Input) -> Result<usize> {
+        Ok(count_unique_digits(configs, &GlyphTable::seven_segment()))
+    }
+
+    fn part2(configs: &Self::Input) -> Result<usize> {
+        let table = GlyphTable::seven_segment();
+        configs.iter().map(|c| c.decode(&table)).sum()
+    }
+}
+
+/// Set of active wires (or segments), wire `a` being the lowest bit.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Pattern(u32);
+
+impl Pattern {
+    fn len(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    fn contains(&self, idx: usize) -> bool {
+        self.0 & (1 << idx) > 0
+    }
+}
+
+impl Parseable for Pattern {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        map(
+            verify(take_while1(|c: char| c.is_ascii_lowercase()), |s: &str| {
+                s.bytes().collect::<HashSet<_>>().len() == s.len()
+            }),
+            |s: &str| Pattern(s.bytes().fold(0, |mask, c| mask | 1 << (c - b'a'))),
+        )(i)
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for idx in 0..u32::BITS as usize {
+            if self.contains(idx) {
+                write!(f, "{}", (b'a' + idx as u8) as char)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Segments lit for each glyph of a display, the glyph's index is its value.
+#[derive(Debug, Clone)]
+pub struct GlyphTable {
+    glyphs: Vec<Pattern>,
+    num_segments: usize,
+}
+
+impl GlyphTable {
+    /// Build a table from the segments of each glyph, written as letters `a` to `z`.
+    pub fn new(glyphs: &[&str]) -> Result<Self> {
+        let glyphs = glyphs
+            .iter()
+            .map(|g| parse_all(g))
+            .collect::<Result<Vec<Pattern>>>()?;
+        if glyphs.iter().collect::<HashSet<_>>().len() != glyphs.len() {
+            bail!("Glyphs are not distinguishable.");
+        }
+        let all = glyphs.iter().fold(0, |all, g| all | g.0);
+        Ok(Self {
+            glyphs,
+            num_segments: (u32::BITS - all.leading_zeros()) as usize,
+        })
+    }
+
+    /// Decimal digits on a seven-segment display.
+    pub fn seven_segment() -> Self {
+        Self::new(&[
+            "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+        ])
+        .unwrap()
+    }
+
+    /// Sizes that only a single glyph has.
+    fn unique_sizes(&self) -> Vec<u32> {
+        let mut counts: HashMap<u32, usize> = HashMap::new();
+        for glyph in self.glyphs.iter() {
+            *counts.entry(glyph.len()).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .filter_map(|(size, count)| (count == 1).then_some(size))
+            .collect()
+    }
+
+    /// Mask of all segments of the display.
+    fn all_segments(&self) -> u32 {
+        match self.num_segments {
+            32 => u32::MAX,
+            size => (1 << size) - 1,
+        }
+    }
+
+    fn value(&self, segments: Pattern) -> Option<usize> {
+        self.glyphs.iter().position(|g| *g == segments)
+    }
+}
+
+/// Which segment each wire is connected to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wiring {
+    wire_to_segment: Vec<usize>,
+}
+
+impl Wiring {
+    fn apply(&self, pattern: Pattern) -> Pattern {
+        Pattern(
+            (0..self.wire_to_segment.len())
+                .filter(|wire| pattern.contains(*wire))
+                .fold(0, |mask, wire| mask | 1 << self.wire_to_segment[wire]),
+        )
+    }
+}
+
+/// Find the only wiring under which every pattern shows a glyph of `table`, there must be one
+/// pattern per glyph.
+///
+/// Candidate segments for each wire are narrowed down by constraint propagation: a wire that is
+/// part of a pattern can only connect to a segment of the glyphs the pattern might show, all other
+/// wires only to segments outside of them. Whatever remains open is settled by a search.
+pub fn solve(table: &GlyphTable, patterns: &[Pattern]) -> Result<Wiring> {
+    if patterns.len() != table.glyphs.len() {
+        bail!(
+            "Expected {} signal patterns, got {}.",
+            table.glyphs.len(),
+            patterns.len()
+        );
+    }
+    check_wires(table, patterns)?;
+    let size = table.num_segments;
+    let all = table.all_segments();
+
+    let mut solver = Solver {
+        table,
+        patterns,
+        candidate_glyphs: patterns
+            .iter()
+            .map(|p| {
+                table
+                    .glyphs
+                    .iter()
+                    .copied()
+                    .filter(|g| g.len() == p.len())
+                    .collect()
+            })
+            .collect(),
+        candidate_segments: vec![all; size],
+    };
+    if !solver.propagate() {
+        bail!("There is no consistent wiring.");
+    }
+
+    let mut solutions = Vec::new();
+    solver.search(&mut vec![None; size], 0, &mut solutions);
+    match solutions.len() {
+        0 => bail!("There is no consistent wiring."),
+        1 => Ok(solutions.pop().unwrap()),
+        _ => bail!("The wiring is ambiguous."),
+    }
+}
+
+/// Fail if any of `patterns` uses a wire the display of `table` does not have.
+fn check_wires(table: &GlyphTable, patterns: &[Pattern]) -> Result<()> {
+    let all = table.all_segments();
+    if let Some(p) = patterns.iter().find(|p| p.0 & !all != 0) {
+        bail!("Pattern {} uses more than {} wires.", p, table.num_segments);
+    }
+    Ok(())
+}
+
+struct Solver<'a> {
+    table: &'a GlyphTable,
+    patterns: &'a [Pattern],
+    candidate_glyphs: Vec<Vec<Pattern>>,
+    // bitmask of segments each wire might be connected to
+    candidate_segments: Vec<u32>,
+}
+
+impl Solver<'_> {
+    /// Narrow down candidates until nothing changes, returns false on contradictions.
+    fn propagate(&mut self) -> bool {
+        let all = self.candidate_segments.iter().fold(0, |all, c| all | c);
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for (pattern, glyphs) in self.patterns.iter().zip(self.candidate_glyphs.iter_mut()) {
+                let segments = &self.candidate_segments;
+                glyphs.retain(|g| {
+                    (0..segments.len()).all(|wire| {
+                        let allowed = if pattern.contains(wire) {
+                            g.0
+                        } else {
+                            all & !g.0
+                        };
+                        segments[wire] & allowed != 0
+                    })
+                });
+                if glyphs.is_empty() {
+                    return false;
+                }
+
+                let inside = glyphs.iter().fold(0, |mask, g| mask | g.0);
+                let outside = glyphs.iter().fold(0, |mask, g| mask | (all & !g.0));
+                for (wire, candidates) in self.candidate_segments.iter_mut().enumerate() {
+                    let allowed = if pattern.contains(wire) {
+                        inside
+                    } else {
+                        outside
+                    };
+                    if *candidates & !allowed != 0 {
+                        *candidates &= allowed;
+                        changed = true;
+                    }
+                }
+            }
+
+            // a segment fixed for one wire is not available to any other
+            for wire in 0..self.candidate_segments.len() {
+                let fixed = self.candidate_segments[wire];
+                if fixed.count_ones() != 1 {
+                    continue;
+                }
+                for (other, candidates) in self.candidate_segments.iter_mut().enumerate() {
+                    if other != wire && *candidates & fixed != 0 {
+                        *candidates &= !fixed;
+                        changed = true;
+                    }
+                }
+            }
+
+            if self.candidate_segments.contains(&0) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether the partial `assignment` still allows each pattern to show one of its glyphs.
+    fn consistent(&self, assignment: &[Option<usize>]) -> bool {
+        self.patterns
+            .iter()
+            .zip(self.candidate_glyphs.iter())
+            .all(|(pattern, glyphs)| {
+                glyphs.iter().any(|g| {
+                    assignment.iter().enumerate().all(|(wire, segment)| {
+                        segment.is_none_or(|s| pattern.contains(wire) == g.contains(s))
+                    })
+                })
+            })
+    }
+
+    /// Collect complete assignments of the remaining wires, stopping after the second one.
+    fn search(&self, assignment: &mut Vec<Option<usize>>, used: u32, found: &mut Vec<Wiring>) {
+        if found.len() > 1 {
+            return;
+        }
+        let next = (0..assignment.len())
+            .filter(|wire| assignment[*wire].is_none())
+            .min_by_key(|wire| (self.candidate_segments[*wire] & !used).count_ones());
+        let wire = match next {
+            Some(wire) => wire,
+            None => {
+                let wiring = Wiring {
+                    wire_to_segment: assignment.iter().map(|s| s.unwrap()).collect(),
+                };
+                // wires might be unused by all patterns, so check the glyphs once more
+                if self
+                    .patterns
+                    .iter()
+                    .all(|p| self.table.value(wiring.apply(*p)).is_some())
+                {
+                    found.push(wiring);
+                }
+                return;
+            }
+        };
+
+        let mut candidates = self.candidate_segments[wire] & !used;
+        while candidates != 0 {
+            let segment = candidates.trailing_zeros() as usize;
+            candidates &= candidates - 1;
+
+            assignment[wire] = Some(segment);
+            if self.consistent(assignment) {
+                self.search(assignment, used | 1 << segment, found);
+            }
+        }
+        assignment[wire] = None;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Configuration {
+    signals: Vec<Pattern>,
+    digits: Vec<Pattern>,
+}
+
+impl Parseable for Configuration {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let (i, signals) = patterns(i)?;
+        let (i, _) = tag(" | ")(i)?;
+        let (i, digits) = patterns(i)?;
+
+        Ok((i, Self { signals, digits }))
+    }
+}
+
+/// Space-separated signal patterns, how many depends on the display.
+fn patterns(i: &str) -> IResult<&str, Vec<Pattern>> {
+    separated_list1(space1, Pattern::parse)(i)
+}
+
+fn read(i: &str) -> Result<Vec<Configuration>> {
+    parse_all_with(separated_list1(line_ending, Configuration::parse), i)
+}
+
+impl Configuration {
+    pub fn new(signals: Vec<Pattern>, digits: Vec<Pattern>) -> Self {
+        Self { signals, digits }
+    }
+
+    /// Value of the displayed digits, in the base given by the number of glyphs in `table`.
+    fn decode(&self, table: &GlyphTable) -> Result<usize> {
+        let wiring = solve(table, &self.signals)?;
+        check_wires(table, &self.digits)?;
+        let mut retval = 0;
+        for digit in self.digits.iter() {
+            retval *= table.glyphs.len();
+            retval += table
+                .value(wiring.apply(*digit))
+                .with_context(|| format!("Pattern {} does not show any glyph.", digit))?;
+        }
+        Ok(retval)
+    }
+}
+
+fn count_unique_digits(configs: &[Configuration], table: &GlyphTable) -> usize {
+    let unique = table.unique_sizes();
+    configs
+        .iter()
+        .map(|c| {
+            c.digits
+                .iter()
+                .filter(|d| unique.contains(&d.len()))
+                .count()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(i: &str) -> Result<Vec<Pattern>> {
+        i.split_whitespace().map(parse_all).collect()
+    }
+
+    #[test]
+    fn test_part1() {
+        let content = read_to_string(PathBuf::from("debug.txt")).unwrap();
+        let configs = read(&content).unwrap();
+        assert_eq!(
+            count_unique_digits(&configs[..], &GlyphTable::seven_segment()),
+            26
+        );
+    }
+
+    #[test]
+    fn test_part2() -> Result<()> {
+        let content = read_to_string(PathBuf::from("debug.txt"))?;
+        let configs = read(&content)?;
+        let table = GlyphTable::seven_segment();
+        assert_eq!(configs.len(), 10);
+        let decoded = configs
+            .iter()
+            .map(|c| c.decode(&table))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(
+            decoded,
+            [8394, 9781, 1197, 9361, 4873, 8418, 4548, 1625, 8717, 4315]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_hex_digits() -> Result<()> {
+        let table = GlyphTable::new(&[
+            "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg",
+            "abcdfg", "abcdef", "bdefg", "abeg", "cdefg", "abdeg", "abde",
+        ])?;
+        let mut unique = table.unique_sizes();
+        unique.sort_unstable();
+        assert_eq!(unique, [2, 3, 7]);
+
+        // rewire a -> d -> g -> b -> e -> a, c <-> f
+        let rewire = Wiring {
+            wire_to_segment: vec![4, 3, 5, 6, 0, 2, 1],
+        };
+        let signals: Vec<_> = table.glyphs.iter().map(|g| rewire.apply(*g)).collect();
+        let solved = solve(&table, &signals)?;
+        for (glyph, signal) in table.glyphs.iter().zip(signals.iter()) {
+            assert_eq!(solved.apply(*signal), *glyph);
+        }
+
+        let digits = [0xc, 0xa, 0xf, 0xe].map(|d| rewire.apply(table.glyphs[d]));
+        let config = Configuration::new(signals, digits.to_vec());
+        assert_eq!(config.decode(&table)?, 0xcafe);
+
+        let line = format!(
+            "{} | {}",
+            config
+                .signals
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+            digits.map(|p| p.to_string()).join(" ")
+        );
+        let configs = read(&line)?;
+        assert_eq!(configs[0].signals.len(), 16);
+        assert_eq!(configs[0].decode(&table)?, 0xcafe);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsolvable() -> Result<()> {
+        let table = GlyphTable::seven_segment();
+
+        let err = solve(&table, &patterns("ab dab")?).unwrap_err();
+        assert!(err.to_string().contains("Expected 10"), "{}", err);
+
+        // swapping both segments maps the table onto itself
+        let symmetric = GlyphTable::new(&["ab", "a", "b"])?;
+        let err = solve(&symmetric, &patterns("ab a b")?).unwrap_err();
+        assert!(err.to_string().contains("ambiguous"), "{}", err);
+
+        // the pattern of 1 is not part of the one of 7
+        let err = solve(
+            &table,
+            &patterns("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ac")?,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("no consistent"), "{}", err);
+
+        let config = Configuration::new(
+            patterns("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab")?,
+            patterns("cdfeb fcadb cdfeb cdbaf")?,
+        );
+        assert_eq!(config.decode(&table)?, 5353);
+        let config = Configuration::new(config.signals, patterns("cdfeb fcadb cdfeb cdbg")?);
+        assert!(config.decode(&table).is_err());
+        // `h` is not connected, without it the pattern would show a 5
+        let config = Configuration::new(config.signals, patterns("cdfebh")?);
+        let err = config.decode(&table).unwrap_err();
+        assert!(err.to_string().contains("more than 7 wires"), "{}", err);
+        Ok(())
+    }
+}
@@ -0,0 +1,437 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{digit, grid, num1, parse_all, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, one_of, space0, space1,
+    },
+    combinator::{map, map_res, value, verify},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Grid;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        Grid::read(i)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<usize> {
+        Ok(grid.get_low_points().iter().map(|p| p.risk_level()).sum())
+    }
+
+    fn part2(grid: &Self::Input) -> Result<usize> {
+        Ok(grid.get_largest_basins().iter().product())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    size_x: usize,
+    size_y: usize,
+    grid: Vec<Vec<usize>>,
+}
+
+impl Parseable for Grid {
+    /// Heights are single digits without separators, or arbitrary numbers separated by spaces if
+    /// the first line contains any.
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let (i, grid) = match i.lines().next() {
+            Some(line) if line.contains(' ') => separated_grid(i)?,
+            _ => grid(digit::<usize>)(i)?,
+        };
+
+        let size_y = grid.len();
+        let size_x = grid[0].len();
+
+        Ok((
+            i,
+            Self {
+                grid,
+                size_x,
+                size_y,
+            },
+        ))
+    }
+}
+
+/// Rectangular grid of space-separated numbers, rows may be indented for alignment.
+fn separated_grid(i: &str) -> IResult<&str, Vec<Vec<usize>>> {
+    verify(
+        separated_list1(line_ending, preceded(space0, separated_list1(space1, num1))),
+        |rows: &Vec<Vec<usize>>| rows.iter().all(|row| row.len() == rows[0].len()),
+    )(i)
+}
+
+impl Grid {
+    fn read(i: &str) -> Result<Self> {
+        parse_all(i)
+    }
+
+    fn get_low_points(&self) -> Vec<Point> {
+        let mut points = Vec::new();
+
+        for y in 0..self.size_y {
+            for x in 0..self.size_x {
+                let height = self.grid[y][x];
+
+                if y > 0 && self.grid[y - 1][x] <= height {
+                    continue;
+                }
+                if x > 0 && self.grid[y][x - 1] <= height {
+                    continue;
+                }
+                if x < self.size_x - 1 && self.grid[y][x + 1] <= height {
+                    continue;
+                }
+                if y < self.size_y - 1 && self.grid[y + 1][x] <= height {
+                    continue;
+                }
+                points.push(Point { height });
+            }
+        }
+
+        points
+    }
+
+    /// Label the connected components of all cells below the wall height.
+    ///
+    /// A first pass joins each cell with its already visited neighbours in a union-find structure,
+    /// a second pass numbers the resulting components in reading order and collects their
+    /// metadata.
+    pub fn basins(&self, rules: BasinRules) -> Basins {
+        let idx = |x: usize, y: usize| y * self.size_x + x;
+        let is_basin = |x: usize, y: usize| self.grid[y][x] < rules.wall_height;
+
+        let mut components = UnionFind::new(self.size_x * self.size_y);
+        for y in 0..self.size_y {
+            for x in (0..self.size_x).filter(|x| is_basin(*x, y)) {
+                // only neighbours visited before need to be joined
+                let mut neighbours = vec![(x.wrapping_sub(1), y), (x, y.wrapping_sub(1))];
+                if rules.diagonal {
+                    neighbours.push((x.wrapping_sub(1), y.wrapping_sub(1)));
+                    neighbours.push((x + 1, y.wrapping_sub(1)));
+                }
+                for (nx, ny) in neighbours {
+                    if nx < self.size_x && ny < self.size_y && is_basin(nx, ny) {
+                        components.union(idx(x, y), idx(nx, ny));
+                    }
+                }
+            }
+        }
+
+        let mut root_to_id = HashMap::new();
+        let mut basins: Vec<Basin> = Vec::new();
+        let mut labels = vec![vec![None; self.size_x]; self.size_y];
+        for (y, row) in labels.iter_mut().enumerate() {
+            for x in (0..self.size_x).filter(|x| is_basin(*x, y)) {
+                let id = *root_to_id
+                    .entry(components.find(idx(x, y)))
+                    .or_insert_with(|| {
+                        basins.push(Basin {
+                            id: basins.len(),
+                            size: 0,
+                            low_point: (x, y),
+                            min: (x, y),
+                            max: (x, y),
+                        });
+                        basins.len() - 1
+                    });
+                row[x] = Some(id);
+
+                let basin = &mut basins[id];
+                basin.size += 1;
+                let (lx, ly) = basin.low_point;
+                if self.grid[y][x] < self.grid[ly][lx] {
+                    basin.low_point = (x, y);
+                }
+                basin.min = (basin.min.0.min(x), basin.min.1.min(y));
+                basin.max = (basin.max.0.max(x), basin.max.1.max(y));
+            }
+        }
+
+        Basins { labels, basins }
+    }
+
+    fn get_basin_sizes(&self) -> Vec<usize> {
+        self.basins(BasinRules::default())
+            .basins
+            .iter()
+            .map(|b| b.size)
+            .collect()
+    }
+
+    fn get_largest_basins(&self) -> Vec<usize> {
+        let mut basin_sizes = self.get_basin_sizes();
+        basin_sizes.sort_unstable();
+        let all_but_three = basin_sizes.len().saturating_sub(3);
+        basin_sizes.into_iter().skip(all_but_three).collect()
+    }
+}
+
+/// Disjoint sets over indices with path halving and union by size.
+#[derive(Debug, Clone)]
+struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        }
+    }
+
+    fn find(&mut self, mut idx: usize) -> usize {
+        while self.parent[idx] != idx {
+            self.parent[idx] = self.parent[self.parent[idx]];
+            idx = self.parent[idx];
+        }
+        idx
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+    }
+}
+
+/// What separates basins from each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BasinRules {
+    /// Cells of at least this height do not belong to any basin.
+    pub wall_height: usize,
+    /// Whether diagonally adjacent cells are connected.
+    pub diagonal: bool,
+}
+
+impl Default for BasinRules {
+    fn default() -> Self {
+        Self {
+            wall_height: 9,
+            diagonal: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Basin {
+    pub id: usize,
+    pub size: usize,
+    /// Lowest cell, the first one in reading order if there are several.
+    pub low_point: (usize, usize),
+    /// Corners of the bounding box (inclusive).
+    pub min: (usize, usize),
+    pub max: (usize, usize),
+}
+
+/// Basin id of every cell (`None` for walls) and the basins themselves, numbered in reading order.
+#[derive(Debug, Clone)]
+pub struct Basins {
+    pub labels: Vec<Vec<Option<usize>>>,
+    pub basins: Vec<Basin>,
+}
+
+impl Basins {
+    /// Render as plain PPM (P3) image, walls are black and each basin gets its own color.
+    ///
+    /// Every image row starts on a new line, which is wrapped to stay within the 70 characters
+    /// the format allows.
+    pub fn to_ppm(&self) -> String {
+        const MAX_LINE: usize = 70;
+        let size_y = self.labels.len();
+        let size_x = self.labels.first().map_or(0, |l| l.len());
+        let mut ppm = format!("P3\n{} {}\n255\n", size_x, size_y);
+        for row in self.labels.iter() {
+            let mut line = String::new();
+            for label in row.iter() {
+                let (r, g, b) = label.map_or((0, 0, 0), color);
+                for value in [r, g, b] {
+                    let value = value.to_string();
+                    if !line.is_empty() && line.len() + 1 + value.len() > MAX_LINE {
+                        ppm += &line;
+                        ppm.push('\n');
+                        line.clear();
+                    }
+                    if !line.is_empty() {
+                        line.push(' ');
+                    }
+                    line += &value;
+                }
+            }
+            ppm += &line;
+            ppm.push('\n');
+        }
+        ppm
+    }
+}
+
+/// Bright color for a basin id, neighbouring ids get quite different colors.
+fn color(id: usize) -> (u8, u8, u8) {
+    let hash = (id as u32 + 1).wrapping_mul(0x9E37_79B9);
+    let channel = |shift: u32| 64 + ((hash >> shift) & 0xff) as u8 % 192;
+    (channel(0), channel(8), channel(16))
+}
+
+impl fmt::Display for Basins {
+    /// Walls are shown as `#`, basins by letters cycling through `a-z` and `A-Z`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const SYMBOLS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        for line in self.labels.iter() {
+            for label in line.iter() {
+                let symbol = label.map_or('#', |id| SYMBOLS[id % SYMBOLS.len()] as char);
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Point {
+    height: usize,
+}
+
+impl Point {
+    fn risk_level(&self) -> usize {
+        self.height + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let content = read_to_string(PathBuf::from("debug.txt")).unwrap();
+        let grid = Grid::read(&content).unwrap();
+        let low_points = grid.get_low_points();
+        assert_eq!(low_points.len(), 4);
+        assert_eq!(low_points.iter().map(|p| p.risk_level()).sum::<usize>(), 15);
+    }
+
+    #[test]
+    fn test_part2() {
+        let content = read_to_string(PathBuf::from("debug.txt")).unwrap();
+        let grid = Grid::read(&content).unwrap();
+        assert_eq!(grid.get_basin_sizes(), [3, 9, 14, 9]);
+        assert_eq!(grid.get_largest_basins().iter().product::<usize>(), 1134);
+    }
+
+    #[test]
+    fn test_basins() -> Result<()> {
+        let grid = Grid::read(&read_to_string(PathBuf::from("debug.txt"))?)?;
+
+        let basins = grid.basins(BasinRules::default());
+        assert_eq!(
+            basins.to_string(),
+            "aa###bbbbb\n\
+             a#ccc#b#bb\n\
+             #ccccc#d#b\n\
+             ccccc#ddd#\n\
+             #c###ddddd\n"
+        );
+        assert_eq!(
+            basins.basins[2],
+            Basin {
+                id: 2,
+                size: 14,
+                low_point: (2, 2),
+                min: (0, 1),
+                max: (5, 4),
+            }
+        );
+        assert_eq!(basins.labels[3][7], Some(3));
+        assert_eq!(basins.labels[0][2], None);
+
+        let low_points: Vec<_> = basins.basins.iter().map(|b| b.low_point).collect();
+        assert_eq!(low_points, [(1, 0), (9, 0), (2, 2), (6, 4)]);
+
+        let separated = Grid::read(
+            " 2  1 99 99\n\
+              3 10 12 99\n\
+             99 11 99  4\n",
+        )?;
+        let tall_walls = separated.basins(BasinRules {
+            wall_height: 99,
+            diagonal: false,
+        });
+        assert_eq!(tall_walls.to_string(), "aa##\naaa#\n#a#b\n");
+        assert_eq!(tall_walls.basins[0].size, 6);
+        assert_eq!(tall_walls.basins[0].low_point, (1, 0));
+        assert!(Grid::read("1 2\n3\n").is_err());
+
+        let diagonal = grid.basins(BasinRules {
+            diagonal: true,
+            ..BasinRules::default()
+        });
+        assert_eq!(diagonal.basins.len(), 1);
+        assert_eq!(diagonal.basins[0].size, 35);
+
+        let low_walls = grid.basins(BasinRules {
+            wall_height: 5,
+            diagonal: false,
+        });
+        assert_eq!(
+            low_walls.to_string(),
+            "aa###bbbbb\n\
+             a#####b#bb\n\
+             #########b\n\
+             ##########\n\
+             ##########\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ppm() -> Result<()> {
+        let grid = Grid::read("19\n91\n")?;
+        let basins = grid.basins(BasinRules::default());
+        let (r, g, b) = color(0);
+        let (r2, g2, b2) = color(1);
+        assert_ne!((r, g, b), (r2, g2, b2));
+        assert_eq!(
+            basins.to_ppm(),
+            format!(
+                "P3\n2 2\n255\n{} {} {} 0 0 0\n0 0 0 {} {} {}\n",
+                r, g, b, r2, g2, b2
+            )
+        );
+
+        let grid = Grid::read(&read_to_string(PathBuf::from("debug.txt"))?)?;
+        let ppm = grid.basins(BasinRules::default()).to_ppm();
+        assert!(ppm.lines().all(|line| line.len() <= 70));
+        let values: Vec<&str> = ppm.split_whitespace().collect();
+        assert_eq!(values.len(), 4 + 10 * 5 * 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,318 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{parse_all_with, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, one_of, space0, space1,
+    },
+    combinator::{map, map_res, value},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        parse_all_with(parse_lines, i)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<u64> {
+        get_score_syntax(&Brackets::default(), lines)
+    }
+
+    fn part2(lines: &Self::Input) -> Result<u64> {
+        get_score_closing(&Brackets::default(), lines)
+    }
+}
+
+fn get_score_syntax(brackets: &Brackets, lines: &[String]) -> Result<u64> {
+    let mut score = 0;
+    for line in lines.iter() {
+        score += brackets.score_syntax(&brackets.check(line)?);
+    }
+    Ok(score)
+}
+
+fn get_score_closing(brackets: &Brackets, lines: &[String]) -> Result<u64> {
+    let mut scores = Vec::new();
+    for line in lines.iter() {
+        let report = brackets.check(line)?;
+        if report.corruption.is_none() && !report.completion.is_empty() {
+            scores.push(brackets.score_completion(&report));
+        }
+    }
+    if scores.is_empty() {
+        bail!("There are no incomplete lines.");
+    }
+    scores.sort_unstable();
+
+    Ok(scores[scores.len() / 2])
+}
+
+/// Pair of matching brackets and the scores of their closing bracket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BracketPair {
+    pub open: char,
+    pub close: char,
+    /// Score if the closing bracket is found where it does not belong.
+    pub syntax_score: u64,
+    /// Score if the closing bracket is needed to complete a line.
+    pub completion_score: u64,
+}
+
+/// All brackets known to the checker.
+#[derive(Debug, Clone)]
+pub struct Brackets {
+    pairs: Vec<BracketPair>,
+    /// Completion scores are computed digit by digit in this base.
+    completion_base: u64,
+}
+
+impl Default for Brackets {
+    fn default() -> Self {
+        let pair = |open, close, syntax_score, completion_score| BracketPair {
+            open,
+            close,
+            syntax_score,
+            completion_score,
+        };
+        Self {
+            pairs: vec![
+                pair('(', ')', 3, 1),
+                pair('[', ']', 57, 2),
+                pair('{', '}', 1197, 3),
+                pair('<', '>', 25137, 4),
+            ],
+            completion_base: 5,
+        }
+    }
+}
+
+/// First closing bracket that does not match the innermost open one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Corruption {
+    /// Byte offset within the line.
+    pub offset: usize,
+    /// Closing bracket of the innermost open one, if any bracket is open at all.
+    pub expected: Option<char>,
+    pub found: char,
+}
+
+impl fmt::Display for Corruption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.expected {
+            Some(expected) => write!(
+                f,
+                "offset {}: expected {}, but found {} instead",
+                self.offset, expected, self.found
+            ),
+            None => write!(
+                f,
+                "offset {}: found {} without open bracket",
+                self.offset, self.found
+            ),
+        }
+    }
+}
+
+/// Result of checking a single line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BracketReport {
+    pub corruption: Option<Corruption>,
+    /// Closing brackets needed to complete the (fixed) line.
+    pub completion: String,
+    /// The line with all mismatched closing brackets replaced by the expected ones (or dropped if
+    /// nothing is open) and the completion appended.
+    pub fixed: String,
+}
+
+impl Brackets {
+    /// Checker for custom bracket pairs, every character may only be used once.
+    pub fn new(pairs: Vec<BracketPair>, completion_base: u64) -> Result<Self> {
+        let chars: HashSet<_> = pairs.iter().flat_map(|p| [p.open, p.close]).collect();
+        if chars.len() != 2 * pairs.len() {
+            bail!("Brackets have to be distinct.");
+        }
+        Ok(Self {
+            pairs,
+            completion_base,
+        })
+    }
+
+    fn opened_by(&self, c: char) -> Option<&BracketPair> {
+        self.pairs.iter().find(|p| p.open == c)
+    }
+
+    fn closed_by(&self, c: char) -> Option<&BracketPair> {
+        self.pairs.iter().find(|p| p.close == c)
+    }
+
+    pub fn check(&self, line: &str) -> Result<BracketReport> {
+        let mut need_to_close: Vec<&BracketPair> = Vec::new();
+        let mut corruption = None;
+        let mut fixed = String::with_capacity(line.len());
+
+        for (offset, c) in line.char_indices() {
+            if let Some(pair) = self.opened_by(c) {
+                need_to_close.push(pair);
+                fixed.push(c);
+                continue;
+            }
+            let pair = self
+                .closed_by(c)
+                .with_context(|| format!("Unknown bracket {} at offset {}", c, offset))?;
+            let current = need_to_close.pop();
+            if current != Some(pair) {
+                // found syntax error
+                corruption.get_or_insert(Corruption {
+                    offset,
+                    expected: current.map(|p| p.close),
+                    found: c,
+                });
+            }
+            if let Some(current) = current {
+                fixed.push(current.close);
+            }
+        }
+
+        let completion: String = need_to_close.iter().rev().map(|p| p.close).collect();
+        fixed.push_str(&completion);
+        Ok(BracketReport {
+            corruption,
+            completion,
+            fixed,
+        })
+    }
+
+    fn score_syntax(&self, report: &BracketReport) -> u64 {
+        report
+            .corruption
+            .and_then(|c| self.closed_by(c.found))
+            .map_or(0, |p| p.syntax_score)
+    }
+
+    fn score_completion(&self, report: &BracketReport) -> u64 {
+        report
+            .completion
+            .chars()
+            .filter_map(|c| self.closed_by(c))
+            .fold(0, |score, p| {
+                score * self.completion_base + p.completion_score
+            })
+    }
+}
+
+fn parse_lines(i: &str) -> IResult<&str, Vec<String>> {
+    separated_list1(
+        line_ending,
+        map(take_while1(|c: char| !c.is_whitespace()), String::from),
+    )(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let content = read_to_string(PathBuf::from("debug.txt")).unwrap();
+        let lines = parse_all_with(parse_lines, &content).unwrap();
+
+        assert_eq!(
+            get_score_syntax(&Brackets::default(), &lines[..]).unwrap(),
+            26397
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        let content = read_to_string(PathBuf::from("debug.txt")).unwrap();
+        let lines = parse_all_with(parse_lines, &content).unwrap();
+
+        assert_eq!(
+            get_score_closing(&Brackets::default(), &lines[..]).unwrap(),
+            288957
+        );
+    }
+
+    #[test]
+    fn test_report() -> Result<()> {
+        let brackets = Brackets::default();
+
+        let report = brackets.check("{([(<{}[<>[]}>{[]{[(<()>")?;
+        let corruption = report.corruption.unwrap();
+        assert_eq!(corruption.offset, 12);
+        assert_eq!((corruption.expected, corruption.found), (Some(']'), '}'));
+        assert_eq!(
+            corruption.to_string(),
+            "offset 12: expected ], but found } instead"
+        );
+        assert_eq!(brackets.score_syntax(&report), 1197);
+        assert_eq!(report.fixed, "{([(<{}[<>[]]>{[]{[(<()>)]}})])}");
+
+        let report = brackets.check("[({(<(())[]>[[{[]{<()<>>")?;
+        assert_eq!(report.corruption, None);
+        assert_eq!(report.completion, "}}]])})]");
+        assert_eq!(brackets.score_completion(&report), 288957);
+        assert_eq!(report.fixed, "[({(<(())[]>[[{[]{<()<>>}}]])})]");
+
+        let report = brackets.check("())")?;
+        assert_eq!(report.corruption.unwrap().expected, None);
+        assert_eq!(report.fixed, "()");
+
+        assert!(brackets.check("(a)").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_brackets() -> Result<()> {
+        let pair = |open, close, syntax_score, completion_score| BracketPair {
+            open,
+            close,
+            syntax_score,
+            completion_score,
+        };
+        let brackets = Brackets::new(vec![pair('/', '\\', 10, 1), pair('«', '»', 20, 2)], 3)?;
+
+        // offsets count bytes, '«' takes two of them
+        let report = brackets.check("«/\\«\\")?;
+        assert_eq!(
+            report.corruption,
+            Some(Corruption {
+                offset: 6,
+                expected: Some('»'),
+                found: '\\',
+            })
+        );
+        assert_eq!(brackets.score_syntax(&report), 10);
+        assert_eq!(report.fixed, "«/\\«»»");
+
+        let report = brackets.check("«//«")?;
+        assert_eq!(report.completion, "»\\\\»");
+        assert_eq!(
+            brackets.score_completion(&report),
+            ((2 * 3 + 1) * 3 + 1) * 3 + 2
+        );
+
+        assert!(Brackets::new(vec![pair('(', ')', 1, 1), pair('[', ')', 1, 1)], 5).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,341 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{digit, grid, parse_all, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, one_of, space0, space1,
+    },
+    combinator::{map, map_res, value},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Grid;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        Grid::read(i)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<usize> {
+        let mut grid = grid.clone();
+        grid.evolve(100);
+        Ok(grid.flashes_total)
+    }
+
+    fn part2(grid: &Self::Input) -> Result<usize> {
+        grid.clone().find_synchronous_flash()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    grid: Vec<Vec<usize>>,
+    size_x: usize,
+    size_y: usize,
+    rules: Rules,
+
+    flashes_total: usize,
+}
+
+/// Octopuses affected by a flash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// Horizontally and vertically adjacent octopuses.
+    Four,
+    /// Diagonally adjacent octopuses as well.
+    Eight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// Octopuses flash once their energy exceeds the threshold.
+    pub threshold: usize,
+    pub neighbourhood: Neighbourhood,
+    /// Whether octopuses at the edges neighbour those at the opposite edge.
+    pub wrap: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            threshold: 9,
+            neighbourhood: Neighbourhood::Eight,
+            wrap: false,
+        }
+    }
+}
+
+/// Error for grids where the octopuses never flash all at once, because their energies repeat
+/// periodically from some round on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NeverSynchronous {
+    /// First round of the cycle.
+    pub start: usize,
+    pub period: usize,
+}
+
+impl fmt::Display for NeverSynchronous {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "octopuses never flash synchronously, energies repeat every {} rounds starting with round {}",
+            self.period, self.start
+        )
+    }
+}
+
+impl std::error::Error for NeverSynchronous {}
+
+impl Parseable for Grid {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let (i, grid) = grid(digit::<usize>)(i)?;
+
+        let size_y = grid.len();
+        let size_x = grid[0].len();
+
+        Ok((
+            i,
+            Self {
+                grid,
+                size_x,
+                size_y,
+                rules: Rules::default(),
+                flashes_total: 0,
+            },
+        ))
+    }
+}
+
+impl Grid {
+    fn read(i: &str) -> Result<Self> {
+        parse_all(i)
+    }
+
+    pub fn with_rules(mut self, rules: Rules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    fn evolve(&mut self, rounds: usize) {
+        for _ in 0..rounds {
+            self.step();
+        }
+    }
+
+    /// Single round, returns the number of flashes.
+    ///
+    /// Flashes are propagated with an explicit stack, every octopus is pushed exactly once when its
+    /// energy exceeds the threshold.
+    fn step(&mut self) -> usize {
+        let threshold = self.rules.threshold;
+        let mut to_flash = Vec::new();
+        for y in 0..self.size_y {
+            for x in 0..self.size_x {
+                self.grid[y][x] += 1;
+                // energies might exceed the threshold from the start
+                if self.grid[y][x] > threshold {
+                    to_flash.push(Point { x, y });
+                }
+            }
+        }
+
+        let mut flashes = 0;
+        while let Some(point) = to_flash.pop() {
+            flashes += 1;
+            for Point { x, y } in self.neighbours(point) {
+                self.grid[y][x] += 1;
+                if self.grid[y][x] == threshold + 1 {
+                    to_flash.push(Point { x, y });
+                }
+            }
+        }
+
+        for energy in self.grid.iter_mut().flatten() {
+            if *energy > threshold {
+                *energy = 0;
+            }
+        }
+        self.flashes_total += flashes;
+        flashes
+    }
+
+    fn neighbours(&self, point: Point) -> Vec<Point> {
+        let mut neighbours = Vec::with_capacity(8);
+        for dy in -1..=1i64 {
+            for dx in -1..=1i64 {
+                if (dx, dy) == (0, 0)
+                    || (self.rules.neighbourhood == Neighbourhood::Four && dx != 0 && dy != 0)
+                {
+                    continue;
+                }
+                let x = point.x as i64 + dx;
+                let y = point.y as i64 + dy;
+                let (size_x, size_y) = (self.size_x as i64, self.size_y as i64);
+                let neighbour = if self.rules.wrap {
+                    Point {
+                        x: x.rem_euclid(size_x) as usize,
+                        y: y.rem_euclid(size_y) as usize,
+                    }
+                } else if (0..size_x).contains(&x) && (0..size_y).contains(&y) {
+                    Point {
+                        x: x as usize,
+                        y: y as usize,
+                    }
+                } else {
+                    continue;
+                };
+                // wrapping around small grids can reach the same octopus (or itself) twice
+                if neighbour != point && !neighbours.contains(&neighbour) {
+                    neighbours.push(neighbour);
+                }
+            }
+        }
+        neighbours
+    }
+
+    /// First round in which all octopuses flash.
+    ///
+    /// Fails with `NeverSynchronous` once the energies repeat without that ever happening.
+    fn find_synchronous_flash(&mut self) -> Result<usize> {
+        let mut seen = HashMap::new();
+        let mut round = 0;
+        loop {
+            if let Some(start) = seen.insert(self.grid.clone(), round) {
+                return Err(NeverSynchronous {
+                    start,
+                    period: round - start,
+                }
+                .into());
+            }
+            round += 1;
+            if self.step() == self.size_x * self.size_y {
+                return Ok(round);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The `f` value implements the `Write` trait, which is what the
+        // write! macro is expecting. Note that this formatting ignores the
+        // various flags provided to format strings.
+        for line in self.grid.iter() {
+            for elem in line.iter() {
+                write!(f, "{}", elem)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+struct Point {
+    x: usize,
+    y: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() -> Result<()> {
+        let mut grid = Grid::read(&read_to_string(PathBuf::from("debug.txt"))?)?;
+
+        assert_eq!(grid.size_x, 10);
+        assert_eq!(grid.size_y, 10);
+
+        grid.evolve(100);
+        assert_eq!(grid.flashes_total, 1656);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> Result<()> {
+        let mut grid = Grid::read(&read_to_string(PathBuf::from("debug.txt"))?)?;
+
+        assert_eq!(grid.find_synchronous_flash()?, 195);
+        Ok(())
+    }
+
+    #[test]
+    fn test_example_steps() -> Result<()> {
+        let mut grid = Grid::read("11111\n19991\n19191\n19991\n11111\n")?;
+
+        assert_eq!(grid.step(), 9);
+        assert_eq!(grid.to_string(), "34543\n40004\n50005\n40004\n34543\n");
+        assert_eq!(grid.step(), 0);
+        assert_eq!(grid.to_string(), "45654\n51115\n61116\n51115\n45654\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_rules() -> Result<()> {
+        let grid = Grid::read(&read_to_string(PathBuf::from("debug.txt"))?)?;
+        let rules = Rules::default();
+
+        let mut four = grid.clone().with_rules(Rules {
+            neighbourhood: Neighbourhood::Four,
+            ..rules
+        });
+        four.evolve(1);
+        assert_eq!(four.flashes_total, 0);
+        four.evolve(1);
+        assert_eq!(four.flashes_total, 20);
+
+        let mut row = Grid::read("9090\n")?.with_rules(Rules {
+            threshold: 9,
+            neighbourhood: Neighbourhood::Four,
+            wrap: true,
+        });
+        assert_eq!(row.step(), 2);
+        assert_eq!(row.to_string(), "0303\n");
+
+        let mut low = Grid::read("12\n30\n")?.with_rules(Rules {
+            threshold: 2,
+            ..rules
+        });
+        assert_eq!(low.step(), 4);
+        assert_eq!(low.to_string(), "00\n00\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_never_synchronous() -> Result<()> {
+        let mut grid = Grid::read("05\n")?.with_rules(Rules {
+            threshold: 9,
+            neighbourhood: Neighbourhood::Four,
+            wrap: false,
+        });
+        let err = grid.find_synchronous_flash().unwrap_err();
+        let err = err.downcast::<NeverSynchronous>().unwrap();
+        assert_eq!(
+            err,
+            NeverSynchronous {
+                start: 0,
+                period: 9
+            }
+        );
+        Ok(())
+    }
+}
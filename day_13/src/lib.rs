@@ -0,0 +1,142 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{num1, parse_all_with, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, one_of, space0, space1,
+    },
+    combinator::{map, map_res, value},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = (Points, Vec<Fold>);
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        parse_all_with(pair(points, folds), i)
+    }
+
+    fn part1((points, instructions): &Self::Input) -> Result<usize> {
+        let instruction = instructions
+            .first()
+            .with_context(|| "No fold instructions.")?;
+        Ok(instruction.apply(points).len())
+    }
+
+    fn part2((points, instructions): &Self::Input) -> Result<String> {
+        let mut points = points.clone();
+        for instr in instructions.iter() {
+            points = instr.apply(&points);
+        }
+        Ok(render_paper(&points))
+    }
+}
+
+type Points = HashSet<(u64, u64)>;
+
+fn render_paper(points: &Points) -> String {
+    let max_x = points.iter().map(|p| p.0).max().unwrap_or(0);
+    let max_y = points.iter().map(|p| p.1).max().unwrap_or(0);
+
+    (0..(max_y + 1))
+        .map(|y| {
+            (0..(max_x + 1))
+                .map(|x| if points.contains(&(x, y)) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn points(i: &str) -> IResult<&str, Points> {
+    let (i, vec) = separated_list1(
+        line_ending,
+        separated_pair(num1::<u64>, char(','), num1::<u64>),
+    )(i)?;
+
+    Ok((i, vec.into_iter().collect()))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Fold {
+    Horizontal(u64),
+    Vertical(u64),
+}
+
+impl Parseable for Fold {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        alt((Fold::parse_vertical, Fold::parse_horizontal))(i)
+    }
+}
+
+fn folds(i: &str) -> IResult<&str, Vec<Fold>> {
+    let (i, _) = many0(line_ending)(i)?;
+    separated_list1(line_ending, Fold::parse)(i)
+}
+
+impl Fold {
+    fn parse_vertical(i: &str) -> IResult<&str, Self> {
+        let (i, _) = tag("fold along y=")(i)?;
+        map(num1, Self::Vertical)(i)
+    }
+
+    fn parse_horizontal(i: &str) -> IResult<&str, Self> {
+        let (i, _) = tag("fold along x=")(i)?;
+        map(num1, Self::Horizontal)(i)
+    }
+
+    pub fn apply(&self, points: &Points) -> Points {
+        match self {
+            Self::Horizontal(fold_x) => points
+                .iter()
+                .map(|(x, y)| (if x < fold_x { *x } else { *fold_x * 2 - x }, *y))
+                .collect(),
+            Self::Vertical(fold_y) => points
+                .iter()
+                .map(|(x, y)| (*x, if y < fold_y { *y } else { *fold_y * 2 - y }))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let content = read_to_string(PathBuf::from("debug.txt")).unwrap();
+        let (i, points) = points(&content).finish().unwrap();
+
+        println!("{:#?}", points);
+
+        assert_eq!(points.len(), 18);
+
+        let folds = folds(i).finish().unwrap().1;
+
+        assert_eq!(folds[0], Fold::Vertical(7));
+        assert_eq!(folds[0].apply(&points).len(), 17);
+    }
+
+    #[test]
+    fn test_part2() {
+    }
+}
@@ -0,0 +1,533 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{parse_all_with, InputBits, ParseError, ParseErrorKind, ParseableBits, Solution};
+use nom::{
+    bits::bits,
+    bits::complete::{tag, take},
+    branch::alt,
+    bytes::complete::{is_a, is_not, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, hex_digit1, line_ending, multispace1, none_of, one_of,
+        space0, space1,
+    },
+    combinator::{map, map_opt, map_res, not, value},
+    error::ParseError as _,
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::{Ord, Ordering, PartialOrd};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Packet;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        let raw = parse_all_with(parse_bytes_from_hex, i)?;
+        // locate errors in the hex input: each byte consists of two hex digits
+        let error_at = |rest: InputBits, kind| {
+            ParseError::at(i, (raw.len() - rest.0.len()) * 2 + rest.1 / 4, kind)
+        };
+        match Packet::parse((&raw[..], 0usize)).finish() {
+            Ok((rest, pkt)) if rest.0.len() <= 1 => Ok(pkt),
+            Ok((rest, _)) => Err(error_at(rest, ParseErrorKind::TrailingInput).into()),
+            Err(e) => Err(error_at(e.input, ParseErrorKind::Nom(e.code)).into()),
+        }
+    }
+
+    fn part1(pkt: &Self::Input) -> Result<usize> {
+        Ok(pkt.version_sum())
+    }
+
+    fn part2(pkt: &Self::Input) -> Result<u64> {
+        Ok(pkt.value())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packet {
+    Literal(LiteralPacket),
+    Operator(OperatorPacket),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiteralPacket {
+    value: u64,
+    version: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatorPacket {
+    type_id: u8,
+    version: u8,
+    packets: Vec<Packet>,
+}
+
+impl ParseableBits for Packet {
+    fn parse(i: (&[u8], usize)) -> IResult<(&[u8], usize), Self> {
+        alt((Self::parse_literal, Self::parse_operator))(i)
+    }
+}
+
+impl Packet {
+    fn parse_literal(i: (&[u8], usize)) -> IResult<(&[u8], usize), Self> {
+        map(LiteralPacket::parse, Packet::Literal)(i)
+    }
+
+    fn parse_operator(i: (&[u8], usize)) -> IResult<(&[u8], usize), Self> {
+        map(OperatorPacket::parse, Packet::Operator)(i)
+    }
+
+    fn version_sum(&self) -> usize {
+        match self {
+            Self::Operator(pkt) => pkt.version_sum(),
+            Self::Literal(pkt) => pkt.version_sum(),
+        }
+    }
+}
+
+impl LiteralPacket {
+    const TYPE_ID: u8 = 4;
+
+    fn version_sum(&self) -> usize {
+        self.version as usize
+    }
+}
+
+impl ParseableBits for LiteralPacket {
+    fn parse(i: InputBits) -> IResult<InputBits, Self> {
+        let (i, version): (InputBits, u8) = take(3usize)(i)?;
+        let (mut i, _) = tag(Self::TYPE_ID, 3usize)(i)?;
+        let mut value: u64 = 0;
+        let mut count = 0;
+
+        let mut is_last_part = false;
+        while !is_last_part {
+            if count == 16 {
+                // cannot store value in 64 bits
+                return Err(nom::Err::Failure(nom::error::Error::from_error_kind(
+                    i,
+                    nom::error::ErrorKind::TooLarge,
+                )));
+            }
+            let (ii, part): (InputBits, u64) = take(5usize)(i)?;
+            i = ii;
+            is_last_part = (part >> 4) == 0;
+            let payload = part & 0xF;
+            value = (value << 4) + payload;
+            if is_last_part {
+                break;
+            }
+            count += 1
+        }
+        Ok((i, Self { version, value }))
+    }
+}
+
+impl OperatorPacket {
+    fn parse(i: InputBits) -> IResult<InputBits, Self> {
+        let (i, version): (InputBits, u8) = take(3usize)(i)?;
+        let _ = not(tag(LiteralPacket::TYPE_ID, 3usize))(i)?;
+        let (i, type_id): (InputBits, u8) = take(3usize)(i)?;
+        let (rest, packets) = subpackets(i)?;
+        let valid_count = match type_id {
+            // comparisons
+            5..=7 => packets.len() == 2,
+            _ => !packets.is_empty(),
+        };
+        if !valid_count {
+            return Err(nom::Err::Failure(nom::error::Error::from_error_kind(
+                i,
+                nom::error::ErrorKind::Count,
+            )));
+        }
+        let i = rest;
+
+        Ok((
+            i,
+            Self {
+                type_id,
+                version,
+                packets,
+            },
+        ))
+    }
+}
+
+fn subpackets(i: InputBits) -> IResult<InputBits, Vec<Packet>> {
+    let (i, size_tag): (InputBits, u8) = take(1usize)(i)?;
+    if size_tag == 0 {
+        let (i, total_bits): (InputBits, usize) = take(15usize)(i)?;
+        let get_error = || {
+            Err(nom::Err::Error(
+                nom::error::Error::<InputBits>::from_error_kind(i, nom::error::ErrorKind::NonEmpty),
+            ))
+        };
+        let (i, bits_subpackets) = take_many(total_bits)(i)?;
+        let parsed_packets = many1(Packet::parse)((&bits_subpackets[..], 0)).finish();
+        if let Ok((ii, packets)) = parsed_packets {
+            let expected_offset = total_bits % 8;
+            if expected_offset != ii.1
+                || (expected_offset == 0 && !ii.0.is_empty())
+                || (expected_offset > 0 && ii.0.len() != 1)
+            {
+                eprintln!(
+                    "parsed {} packets: ii.0.len(): {}, expected_offset: {}, actual: {}",
+                    packets.len(),
+                    ii.0.len(),
+                    expected_offset,
+                    ii.1
+                );
+                get_error()
+            } else {
+                Ok((i, packets))
+            }
+        } else {
+            get_error()
+        }
+    } else {
+        let (mut i, total_packets): (InputBits, usize) = take(11usize)(i)?;
+
+        let mut packets = Vec::new();
+        for _ in 0..total_packets {
+            let (ii, packet) = Packet::parse(i)?;
+            packets.push(packet);
+            i = ii;
+        }
+
+        Ok((i, packets))
+    }
+}
+
+impl OperatorPacket {
+    fn version_sum(&self) -> usize {
+        let packet_sum: usize = self.packets.iter().map(|p: &Packet| p.version_sum()).sum();
+        self.version as usize + packet_sum
+    }
+}
+
+/// Bytes given as pairs of hex digits.
+fn parse_bytes_from_hex(i: &str) -> IResult<&str, Vec<u8>> {
+    many1(map(pair(hex_nibble, hex_nibble), |(high, low)| {
+        (high << 4) + low
+    }))(i)
+}
+
+fn hex_nibble(i: &str) -> IResult<&str, u8> {
+    map_opt(anychar, |c| c.to_digit(16).map(|d| d as u8))(i)
+}
+
+fn take_many<'a>(count: usize) -> impl Fn(InputBits<'a>) -> IResult<InputBits<'a>, Vec<u8>> {
+    move |mut i: InputBits<'a>| -> IResult<InputBits<'a>, Vec<u8>> {
+        let mut values: Vec<u8> = Vec::new();
+        let mut to_read = count;
+
+        while to_read > 8 {
+            let (ii, val): (InputBits, u8) = take(8usize)(i)?;
+            values.push(val);
+            i = ii;
+            to_read -= 8;
+        }
+        let (i, last): (InputBits, u8) = take(to_read)(i)?;
+        values.push(last << (8 - to_read));
+        Ok((i, values))
+    }
+}
+
+trait Evaluatable {
+    fn value(&self) -> u64;
+}
+
+impl Evaluatable for Packet {
+    fn value(&self) -> u64 {
+        match self {
+            Self::Literal(pkt) => pkt.value(),
+            Self::Operator(pkt) => pkt.value(),
+        }
+    }
+}
+
+impl Evaluatable for LiteralPacket {
+    fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+impl Evaluatable for OperatorPacket {
+    fn value(&self) -> u64 {
+        match self.type_id {
+            0 => {
+                // sum
+                self.packets.iter().map(|p| p.value()).sum()
+            }
+            1 => {
+                // product
+                self.packets.iter().map(|p| p.value()).product()
+            }
+            2 => {
+                // minimum
+                self.packets.iter().map(|p| p.value()).min().unwrap()
+            }
+            3 => {
+                // maximum
+                self.packets.iter().map(|p| p.value()).max().unwrap()
+            }
+            5 => {
+                // greater-than
+                if self.packets[0].value() > self.packets[1].value() {
+                    1
+                } else {
+                    0
+                }
+            }
+            6 => {
+                // less-than
+                if self.packets[0].value() < self.packets[1].value() {
+                    1
+                } else {
+                    0
+                }
+            }
+            7 => {
+                // equal
+                if self.packets[0].value() == self.packets[1].value() {
+                    1
+                } else {
+                    0
+                }
+            }
+            _ => panic!("Encountered invalid type id: {}", self.type_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_to_bytes_must(i: &str) -> Vec<u8> {
+        parse_bytes_from_hex(i).finish().unwrap().1
+    }
+
+    #[test]
+    fn test_parsing() {
+        assert_eq!(parse_bytes_from_hex("10").finish().unwrap().1, [0x10]);
+        assert_eq!(parse_bytes_from_hex("AB").finish().unwrap().1, [0xAB]);
+        assert_eq!(parse_bytes_from_hex("ef").finish().unwrap().1, [0xef]);
+        assert_eq!(
+            parse_bytes_from_hex("1234").finish().unwrap().1,
+            [0x12, 0x34]
+        );
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let column = |i: &str| {
+            Day16::parse(i)
+                .unwrap_err()
+                .downcast::<ParseError>()
+                .unwrap()
+                .column
+        };
+        assert_eq!(column("12G4"), 3, "invalid hex digit");
+        assert_eq!(column("D2FE2\n"), 5, "odd number of hex digits");
+        assert_eq!(column("D2FE"), 1, "packet cut off");
+        assert_eq!(column("16004408"), 2, "comparison of a single packet");
+        assert_eq!(column("0A0000"), 2, "minimum of no packets");
+    }
+
+    #[test]
+    fn test_take_many() {
+        let given: Vec<u8> = vec![0xab; 10];
+        let want: Vec<u8> = {
+            let mut want = vec![0xab; 9];
+            want[8] = 0xab & (u8::MAX << 2);
+            want
+        };
+        assert_eq!(
+            take_many(70usize)((&given[..], 0)),
+            Ok(((&given[8..], 6usize), want))
+        );
+    }
+
+    #[test]
+    fn test_part1_1() {
+        let input = hex_to_bytes_must("8A004A801A8002F478");
+        let got = Packet::parse((&input[..], 0)).finish().unwrap().1;
+        assert_eq!(got.version_sum(), 16);
+    }
+
+    #[test]
+    fn test_part1_2() {
+        let input = hex_to_bytes_must("620080001611562C8802118E34");
+        let got = Packet::parse((&input[..], 0)).finish().unwrap().1;
+        assert_eq!(got.version_sum(), 12);
+    }
+
+    #[test]
+    fn test_part1_3() {
+        let input = hex_to_bytes_must("C0015000016115A2E0802F182340");
+        let got = Packet::parse((&input[..], 0)).finish().unwrap().1;
+        assert_eq!(got.version_sum(), 23);
+    }
+
+    #[test]
+    fn test_part1_4() {
+        let input = hex_to_bytes_must("A0016C880162017C3686B18A3D4780");
+        let got = Packet::parse((&input[..], 0)).finish().unwrap().1;
+        assert_eq!(got.version_sum(), 31);
+    }
+
+    #[test]
+    fn test_part1_5() {
+        let input = hex_to_bytes_must("EE00D40C823060");
+        let want = Packet::Operator(OperatorPacket {
+            version: 7,
+            type_id: 3,
+            packets: vec![
+                Packet::Literal(LiteralPacket {
+                    value: 1,
+                    version: 2,
+                }),
+                Packet::Literal(LiteralPacket {
+                    value: 2,
+                    version: 4,
+                }),
+                Packet::Literal(LiteralPacket {
+                    value: 3,
+                    version: 1,
+                }),
+            ],
+        });
+        let got = Packet::parse((&input[..], 0)).finish().unwrap().1;
+        assert_eq!(want, got);
+    }
+
+    #[test]
+    fn test_part1_6() {
+        let input = hex_to_bytes_must("38006F45291200");
+        let want = Packet::Operator(OperatorPacket {
+            version: 1,
+            type_id: 6,
+            packets: vec![
+                Packet::Literal(LiteralPacket {
+                    value: 10,
+                    version: 6,
+                }),
+                Packet::Literal(LiteralPacket {
+                    value: 20,
+                    version: 2,
+                }),
+            ],
+        });
+        let got = Packet::parse((&input[..], 0)).finish().unwrap().1;
+        assert_eq!(want, got);
+    }
+
+    #[test]
+    fn test_part2_1() {
+        assert_eq!(
+            Packet::parse((&hex_to_bytes_must("C200B40A82")[..], 0))
+                .finish()
+                .unwrap()
+                .1
+                .value(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_part2_2() {
+        assert_eq!(
+            Packet::parse((&hex_to_bytes_must("04005AC33890")[..], 0))
+                .finish()
+                .unwrap()
+                .1
+                .value(),
+            54
+        );
+    }
+
+    #[test]
+    fn test_part2_3() {
+        assert_eq!(
+            Packet::parse((&hex_to_bytes_must("880086C3E88112")[..], 0))
+                .finish()
+                .unwrap()
+                .1
+                .value(),
+            7
+        );
+    }
+
+    #[test]
+    fn test_part2_4() {
+        assert_eq!(
+            Packet::parse((&hex_to_bytes_must("CE00C43D881120")[..], 0))
+                .finish()
+                .unwrap()
+                .1
+                .value(),
+            9
+        );
+    }
+
+    #[test]
+    fn test_part2_5() {
+        assert_eq!(
+            Packet::parse((&hex_to_bytes_must("D8005AC2A8F0")[..], 0))
+                .finish()
+                .unwrap()
+                .1
+                .value(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_part2_6() {
+        assert_eq!(
+            Packet::parse((&hex_to_bytes_must("F600BC2D8F")[..], 0))
+                .finish()
+                .unwrap()
+                .1
+                .value(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_part2_7() {
+        assert_eq!(
+            Packet::parse((&hex_to_bytes_must("9C005AC2F8F0")[..], 0))
+                .finish()
+                .unwrap()
+                .1
+                .value(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_part2_8() {
+        assert_eq!(
+            Packet::parse((&hex_to_bytes_must("9C0141080250320F1802104A08")[..], 0))
+                .finish()
+                .unwrap()
+                .1
+                .value(),
+            1
+        );
+    }
+}
@@ -0,0 +1,76 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{parse_all, signed, Parseable, Solution};
+use nom::{
+    bytes::complete::tag,
+    sequence::{preceded, separated_pair},
+    IResult,
+};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = TargetArea;
+    type Answer1 = i64;
+    type Answer2 = usize;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        parse_all(i)
+    }
+
+    fn part1(target: &Self::Input) -> Result<i64> {
+        Ok(find_ymax(target.x, target.y).0)
+    }
+
+    fn part2(target: &Self::Input) -> Result<usize> {
+        Ok(find_ymax(target.x, target.y).1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetArea {
+    x: (i64, i64),
+    y: (i64, i64),
+}
+
+impl Parseable for TargetArea {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let range = || separated_pair(signed, tag(".."), signed);
+        let (i, x) = preceded(tag("target area: x="), range())(i)?;
+        let (i, y) = preceded(tag(", y="), range())(i)?;
+        Ok((i, Self { x, y }))
+    }
+}
+
+fn find_ymax((x_min, x_max): (i64, i64), (y_min, y_max): (i64, i64)) -> (i64, usize) {
+    let mut ymax_observed = 0;
+    let mut num_valid_velocities = 0;
+    for vx0 in 0..1000 {
+        for vy0 in -1000..1000 {
+            let mut ymax_local = 0;
+            let (mut vx, mut vy) = (vx0, vy0);
+            let (mut x, mut y): (i64, i64) = (0, 0);
+            while x < x_max && y > y_min {
+                x += vx;
+                y += vy;
+                if y > ymax_local {
+                    ymax_local = y;
+                }
+                if vx > 0 {
+                    vx -= 1;
+                }
+                vy -= 1;
+                if x >= x_min && x <= x_max && y >= y_min && y <= y_max {
+                    if ymax_observed < ymax_local {
+                        ymax_observed = ymax_local;
+                    }
+                    num_valid_velocities += 1;
+                    break;
+                }
+            }
+        }
+    }
+    (ymax_observed, num_valid_velocities)
+}
@@ -0,0 +1,206 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{grid, parse_all, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, not_line_ending, one_of,
+        space0, space1,
+    },
+    combinator::{map, map_res, value, verify},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = Image;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        Image::read(i)
+    }
+
+    fn part1(image: &Self::Input) -> Result<usize> {
+        let mut image = image.clone();
+        image.enhance(2);
+        image.num_lit()
+    }
+
+    fn part2(image: &Self::Input) -> Result<usize> {
+        let mut image = image.clone();
+        image.enhance(50);
+        image.num_lit()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    algorithm: Vec<bool>,
+    pixels: Vec<Vec<bool>>,
+    size_x: usize,
+    size_y: usize,
+
+    // state of all pixels outside of `pixels`, which flips each step if the algorithm maps dark
+    // neighbourhoods to lit pixels
+    background: bool,
+}
+
+impl Parseable for Image {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let (i, algorithm) = verify(many1(pixel), |a: &Vec<bool>| a.len() == 512)(i)?;
+        let (i, _) = pair(line_ending, line_ending)(i)?;
+        let (i, pixels) = grid(pixel)(i)?;
+
+        let size_y = pixels.len();
+        let size_x = pixels[0].len();
+
+        Ok((
+            i,
+            Self {
+                algorithm,
+                pixels,
+                size_x,
+                size_y,
+                background: false,
+            },
+        ))
+    }
+}
+
+fn pixel(i: &str) -> IResult<&str, bool> {
+    alt((value(true, char('#')), value(false, char('.'))))(i)
+}
+
+impl Image {
+    fn read(i: &str) -> Result<Self> {
+        parse_all(i)
+    }
+
+    fn enhance(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.enhance_once();
+        }
+    }
+
+    fn enhance_once(&mut self) {
+        // the image grows by one pixel on each side per step
+        let pixels = (0..self.size_y + 2)
+            .map(|y| {
+                (0..self.size_x + 2)
+                    .map(|x| self.algorithm[self.index(x as i64 - 1, y as i64 - 1)])
+                    .collect()
+            })
+            .collect();
+
+        self.background = if self.background {
+            self.algorithm[511]
+        } else {
+            self.algorithm[0]
+        };
+        self.pixels = pixels;
+        self.size_x += 2;
+        self.size_y += 2;
+    }
+
+    /// Index into the algorithm for the 3x3 neighbourhood around (x, y).
+    fn index(&self, x: i64, y: i64) -> usize {
+        let mut index = 0;
+        for dy in -1..2 {
+            for dx in -1..2 {
+                index = (index << 1) + self.get(x + dx, y + dy) as usize;
+            }
+        }
+        index
+    }
+
+    fn get(&self, x: i64, y: i64) -> bool {
+        if x < 0 || y < 0 || x >= self.size_x as i64 || y >= self.size_y as i64 {
+            self.background
+        } else {
+            self.pixels[y as usize][x as usize]
+        }
+    }
+
+    fn num_lit(&self) -> Result<usize> {
+        if self.background {
+            bail!("Infinitely many pixels are lit.");
+        }
+        Ok(self.pixels.iter().flatten().filter(|p| **p).count())
+    }
+}
+
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in self.pixels.iter() {
+            for lit in line.iter() {
+                write!(f, "{}", if *lit { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() -> Result<()> {
+        let mut image = Image::read(&read_to_string(PathBuf::from("debug.txt"))?)?;
+
+        assert_eq!(image.size_x, 5);
+        assert_eq!(image.size_y, 5);
+
+        image.enhance(1);
+        assert_eq!(
+            image.to_string(),
+            ".##.##.\n#..#.#.\n##.#..#\n####..#\n.#..##.\n..##..#\n...#.#.\n"
+        );
+
+        image.enhance(1);
+        assert_eq!(image.num_lit()?, 35);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> Result<()> {
+        let mut image = Image::read(&read_to_string(PathBuf::from("debug.txt"))?)?;
+
+        image.enhance(50);
+        assert_eq!(image.num_lit()?, 3351);
+        Ok(())
+    }
+
+    #[test]
+    fn test_flipping_background() -> Result<()> {
+        // only completely dark neighbourhoods light up
+        let algorithm: String = (0..512).map(|i| if i == 0 { '#' } else { '.' }).collect();
+        let mut image = Image::read(&format!("{}\n\n...\n.#.\n...\n", algorithm))?;
+
+        image.enhance(1);
+        assert!(image.num_lit().is_err());
+        image.enhance(1);
+        assert_eq!(
+            image.num_lit()?,
+            1,
+            "only the center is surrounded by dark pixels"
+        );
+        Ok(())
+    }
+}
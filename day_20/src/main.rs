@@ -0,0 +1,6 @@
+use anyhow::Result;
+use day_20::Day20;
+
+fn main() -> Result<()> {
+    aoc_common::main::<Day20>()
+}
@@ -0,0 +1,174 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{num1, parse_all, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, not_line_ending, one_of,
+        space0, space1,
+    },
+    combinator::{map, map_res, value, verify},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input = Game;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        Game::read(i)
+    }
+
+    fn part1(game: &Self::Input) -> Result<u64> {
+        Ok(game.play_deterministic())
+    }
+
+    fn part2(game: &Self::Input) -> Result<u64> {
+        Ok(game.count_wins().into_iter().max().unwrap_or_default())
+    }
+}
+
+const TRACK_LENGTH: u64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Game {
+    start: [u64; 2],
+}
+
+impl Parseable for Game {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let position = || verify(num1, |p: &u64| (1..=TRACK_LENGTH).contains(p));
+        let (i, first) = preceded(tag("Player 1 starting position: "), position())(i)?;
+        let (i, _) = line_ending(i)?;
+        let (i, second) = preceded(tag("Player 2 starting position: "), position())(i)?;
+        Ok((
+            i,
+            Self {
+                start: [first, second],
+            },
+        ))
+    }
+}
+
+/// Snapshot of a game of Dirac Dice right before `turn`'s player rolls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    positions: [u64; 2],
+    scores: [u64; 2],
+    turn: usize,
+}
+
+impl State {
+    fn advance(&self, steps: u64) -> Self {
+        let mut next = *self;
+        let pos = &mut next.positions[self.turn];
+        *pos = (*pos + steps - 1) % TRACK_LENGTH + 1;
+        next.scores[self.turn] += *pos;
+        next.turn = 1 - self.turn;
+        next
+    }
+}
+
+impl Game {
+    fn read(i: &str) -> Result<Self> {
+        parse_all(i)
+    }
+
+    fn initial_state(&self) -> State {
+        State {
+            positions: self.start,
+            scores: [0, 0],
+            turn: 0,
+        }
+    }
+
+    /// Play with the deterministic 100-sided die up to 1000 points, returning the score of the
+    /// losing player times the number of rolls.
+    fn play_deterministic(&self) -> u64 {
+        let mut state = self.initial_state();
+        let mut die = (1..=100).cycle();
+        let mut rolls = 0;
+
+        loop {
+            let player = state.turn;
+            state = state.advance(die.by_ref().take(3).sum());
+            rolls += 3;
+            if state.scores[player] >= 1000 {
+                return state.scores[1 - player] * rolls;
+            }
+        }
+    }
+
+    /// Number of universes in which each player wins with the Dirac die (up to 21 points).
+    fn count_wins(&self) -> [u64; 2] {
+        count_wins(self.initial_state(), &mut HashMap::new())
+    }
+}
+
+/// How often each sum of three rolls of the three-sided Dirac die occurs.
+const DIRAC_ROLLS: [(u64, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+fn count_wins(state: State, memo: &mut HashMap<State, [u64; 2]>) -> [u64; 2] {
+    if let Some(wins) = memo.get(&state) {
+        return *wins;
+    }
+
+    let mut wins = [0, 0];
+    for (steps, universes) in DIRAC_ROLLS {
+        let next = state.advance(steps);
+        if next.scores[state.turn] >= 21 {
+            wins[state.turn] += universes;
+        } else {
+            let sub_wins = count_wins(next, memo);
+            wins[0] += universes * sub_wins[0];
+            wins[1] += universes * sub_wins[1];
+        }
+    }
+    memo.insert(state, wins);
+    wins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() -> Result<()> {
+        let game = Game::read(&read_to_string(PathBuf::from("debug.txt"))?)?;
+
+        assert_eq!(game.start, [4, 8]);
+        assert_eq!(game.play_deterministic(), 739785);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> Result<()> {
+        let game = Game::read(&read_to_string(PathBuf::from("debug.txt"))?)?;
+
+        assert_eq!(game.count_wins(), [444356092776315, 341960390180808]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_position() {
+        assert!(
+            Game::read("Player 1 starting position: 11\nPlayer 2 starting position: 8\n").is_err()
+        );
+    }
+}
@@ -0,0 +1,193 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{parse_all_with, signed, Parseable, Position, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, not_line_ending, one_of,
+        space0, space1,
+    },
+    combinator::{map, map_res, value, verify},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Input = Vec<Step>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        parse_all_with(steps, i)
+    }
+
+    fn part1(steps: &Self::Input) -> Result<i64> {
+        let region = Cuboid {
+            min: Position::new(-50, -50, -50),
+            max: Position::new(50, 50, 50),
+        };
+        Ok(count_lit(steps, Some(&region)))
+    }
+
+    fn part2(steps: &Self::Input) -> Result<i64> {
+        Ok(count_lit(steps, None))
+    }
+}
+
+/// Axis-aligned cuboid, both corners are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cuboid {
+    min: Position,
+    max: Position,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    on: bool,
+    cuboid: Cuboid,
+}
+
+impl Cuboid {
+    fn intersect(&self, other: &Self) -> Option<Self> {
+        let min = Position::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Position::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Self { min, max })
+    }
+
+    fn volume(&self) -> i64 {
+        let size = self.max - self.min;
+        (size.x + 1) * (size.y + 1) * (size.z + 1)
+    }
+}
+
+impl Parseable for Cuboid {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let range = |axis| {
+            preceded(
+                pair(char(axis), char('=')),
+                verify(separated_pair(signed, tag(".."), signed), |(l, r)| l <= r),
+            )
+        };
+        let (i, (x, _, y, _, z)) =
+            tuple((range('x'), char(','), range('y'), char(','), range('z')))(i)?;
+        Ok((
+            i,
+            Self {
+                min: Position::new(x.0, y.0, z.0),
+                max: Position::new(x.1, y.1, z.1),
+            },
+        ))
+    }
+}
+
+impl Parseable for Step {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let (i, on) = alt((value(true, tag("on")), value(false, tag("off"))))(i)?;
+        let (i, cuboid) = preceded(space1, Cuboid::parse)(i)?;
+        Ok((i, Self { on, cuboid }))
+    }
+}
+
+fn steps(i: &str) -> IResult<&str, Vec<Step>> {
+    separated_list1(line_ending, Step::parse)(i)
+}
+
+/// Number of lit cubes after all `steps`, only counting cubes inside `region` (if given).
+///
+/// Instead of tracking single cubes, lit volume is tracked as a list of signed cuboids: every new
+/// step cancels its overlap with all previous cuboids (adding back doubly removed overlaps) and
+/// is then added itself if it turns cubes on.
+fn count_lit(steps: &[Step], region: Option<&Cuboid>) -> i64 {
+    let mut signed_cuboids: Vec<(Cuboid, i64)> = Vec::new();
+
+    for step in steps {
+        let cuboid = match region {
+            Some(region) => match step.cuboid.intersect(region) {
+                Some(cuboid) => cuboid,
+                None => continue,
+            },
+            None => step.cuboid,
+        };
+
+        let overlaps: Vec<_> = signed_cuboids
+            .iter()
+            .filter_map(|(other, sign)| Some((other.intersect(&cuboid)?, -sign)))
+            .collect();
+        signed_cuboids.extend(overlaps);
+        if step.on {
+            signed_cuboids.push((cuboid, 1));
+        }
+    }
+
+    signed_cuboids
+        .iter()
+        .map(|(cuboid, sign)| sign * cuboid.volume())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() -> Result<()> {
+        let steps = Day22::parse(&read_to_string(PathBuf::from("debug.txt"))?)?;
+
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[0].cuboid.volume(), 27);
+        assert_eq!(Day22::part1(&steps)?, 39);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> Result<()> {
+        // cross-check against counting single cubes
+        let steps = Day22::parse(
+            "on x=-5..5,y=0..3,z=-2..2\n\
+             off x=0..7,y=-1..1,z=0..9\n\
+             on x=-3..1,y=-3..1,z=-3..1\n\
+             on x=2..9,y=2..9,z=-9..0\n\
+             off x=-9..-4,y=-9..9,z=-1..1\n\
+             on x=-1..1,y=-1..1,z=-1..1\n",
+        )?;
+        let mut lit = HashSet::new();
+        for step in steps.iter() {
+            let Cuboid { min, max } = step.cuboid;
+            for x in min.x..=max.x {
+                for y in min.y..=max.y {
+                    for z in min.z..=max.z {
+                        if step.on {
+                            lit.insert((x, y, z));
+                        } else {
+                            lit.remove(&(x, y, z));
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(Day22::part2(&steps)?, lit.len() as i64);
+        Ok(())
+    }
+}
@@ -0,0 +1,6 @@
+use anyhow::Result;
+use day_22::Day22;
+
+fn main() -> Result<()> {
+    aoc_common::main::<Day22>()
+}
@@ -0,0 +1,354 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{parse_all, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, not_line_ending, one_of,
+        space0, space1,
+    },
+    combinator::{map, map_res, value, verify},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Input = Burrow;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        Burrow::read(i)
+    }
+
+    fn part1(burrow: &Self::Input) -> Result<usize> {
+        burrow
+            .lowest_energy()
+            .with_context(|| "amphipods cannot be organized")
+    }
+
+    fn part2(burrow: &Self::Input) -> Result<usize> {
+        burrow
+            .unfold()
+            .lowest_energy()
+            .with_context(|| "amphipods cannot be organized")
+    }
+}
+
+const HALLWAY_LENGTH: usize = 11;
+const NUM_ROOMS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+impl Amphipod {
+    fn energy(&self) -> usize {
+        match self {
+            Amphipod::Amber => 1,
+            Amphipod::Bronze => 10,
+            Amphipod::Copper => 100,
+            Amphipod::Desert => 1000,
+        }
+    }
+
+    /// Index of the room the amphipod wants to end up in.
+    fn room(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl Parseable for Amphipod {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        alt((
+            value(Amphipod::Amber, char('A')),
+            value(Amphipod::Bronze, char('B')),
+            value(Amphipod::Copper, char('C')),
+            value(Amphipod::Desert, char('D')),
+        ))(i)
+    }
+}
+
+impl fmt::Display for Amphipod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Amphipod::Amber => 'A',
+            Amphipod::Bronze => 'B',
+            Amphipod::Copper => 'C',
+            Amphipod::Desert => 'D',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+type Space = Option<Amphipod>;
+
+fn space(i: &str) -> IResult<&str, Space> {
+    alt((value(None, char('.')), map(Amphipod::parse, Some)))(i)
+}
+
+/// Hallway with the four side rooms, rooms are stored top (next to the hallway) to bottom.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Burrow {
+    hallway: [Space; HALLWAY_LENGTH],
+    rooms: [Vec<Space>; NUM_ROOMS],
+}
+
+impl Parseable for Burrow {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let (i, _) = pair(tag("#############"), line_ending)(i)?;
+        let (i, hallway) = map_res(
+            delimited(char('#'), many1(space), pair(char('#'), line_ending)),
+            <[Space; HALLWAY_LENGTH]>::try_from,
+        )(i)?;
+
+        let room_row = delimited(
+            alt((tag("###"), tag("  #"))),
+            verify(separated_list1(char('#'), space), |row: &Vec<_>| {
+                row.len() == NUM_ROOMS
+            }),
+            pair(alt((tag("###"), tag("#"))), space0),
+        );
+        let (i, rows) = many1(terminated(room_row, line_ending))(i)?;
+        let (i, _) = pair(tag("  #########"), space0)(i)?;
+
+        let rooms = [0, 1, 2, 3].map(|room| rows.iter().map(|row| row[room]).collect());
+        Ok((i, Self { hallway, rooms }))
+    }
+}
+
+impl fmt::Display for Burrow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let space = |s: &Space| s.map_or(".".to_owned(), |a| a.to_string());
+
+        writeln!(f, "#############")?;
+        write!(f, "#")?;
+        for s in self.hallway.iter() {
+            write!(f, "{}", space(s))?;
+        }
+        writeln!(f, "#")?;
+        for depth in 0..self.depth() {
+            write!(f, "{}", if depth == 0 { "##" } else { "  " })?;
+            for room in self.rooms.iter() {
+                write!(f, "#{}", space(&room[depth]))?;
+            }
+            writeln!(f, "{}", if depth == 0 { "###" } else { "#" })?;
+        }
+        writeln!(f, "  #########")
+    }
+}
+
+/// State in the search, ordered by energy spent so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Candidate {
+    energy: usize,
+    burrow: Burrow,
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.energy
+            .cmp(&other.energy)
+            .then_with(|| self.burrow.cmp(&other.burrow))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Burrow {
+    fn read(i: &str) -> Result<Self> {
+        parse_all(i)
+    }
+
+    fn depth(&self) -> usize {
+        self.rooms[0].len()
+    }
+
+    /// Insert the two rows hidden by the folded diagram.
+    fn unfold(&self) -> Self {
+        use Amphipod::*;
+        let inserted = [
+            [Desert, Desert],
+            [Copper, Bronze],
+            [Bronze, Amber],
+            [Amber, Copper],
+        ];
+
+        let mut unfolded = self.clone();
+        for (room, extra) in unfolded.rooms.iter_mut().zip(inserted) {
+            room.splice(1..1, extra.map(Some));
+        }
+        unfolded
+    }
+
+    /// Hallway position right outside of the given room.
+    fn entrance(room: usize) -> usize {
+        2 + 2 * room
+    }
+
+    fn is_organized(&self) -> bool {
+        self.rooms
+            .iter()
+            .enumerate()
+            .all(|(idx, room)| room.iter().all(|s| s.map(|a| a.room()) == Some(idx)))
+    }
+
+    /// Whether the room only contains amphipods that belong there.
+    fn accepts_guests(&self, room: usize) -> bool {
+        self.rooms[room].iter().flatten().all(|a| a.room() == room)
+    }
+
+    /// Whether the hallway is free between `from` (exclusive) and `to` (inclusive).
+    fn hallway_free(&self, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..to + 1
+        } else {
+            to..from
+        };
+        self.hallway[range].iter().all(|s| s.is_none())
+    }
+
+    /// All states reachable by moving a single amphipod, together with the required energy.
+    fn moves(&self) -> Vec<(Burrow, usize)> {
+        let mut moves = Vec::new();
+
+        // from the hallway into the target room
+        for (pos, amphipod) in self.hallway.iter().enumerate() {
+            let amphipod = match amphipod {
+                Some(amphipod) => *amphipod,
+                None => continue,
+            };
+            let room = amphipod.room();
+            let entrance = Self::entrance(room);
+            if !self.accepts_guests(room) || !self.hallway_free(pos, entrance) {
+                continue;
+            }
+            if let Some(depth) = self.rooms[room].iter().rposition(|s| s.is_none()) {
+                let mut next = self.clone();
+                next.hallway[pos] = None;
+                next.rooms[room][depth] = Some(amphipod);
+                let steps = pos.abs_diff(entrance) + depth + 1;
+                moves.push((next, steps * amphipod.energy()));
+            }
+        }
+
+        // from the top of a room into the hallway
+        for room in 0..NUM_ROOMS {
+            if self.accepts_guests(room) {
+                continue;
+            }
+            let (depth, amphipod) = match self.rooms[room]
+                .iter()
+                .enumerate()
+                .find_map(|(depth, s)| Some((depth, (*s)?)))
+            {
+                Some(top) => top,
+                None => continue,
+            };
+            let entrance = Self::entrance(room);
+            for pos in 0..HALLWAY_LENGTH {
+                // amphipods never stop right outside of a room
+                if (0..NUM_ROOMS).any(|r| Self::entrance(r) == pos)
+                    || !self.hallway_free(entrance, pos)
+                {
+                    continue;
+                }
+                let mut next = self.clone();
+                next.rooms[room][depth] = None;
+                next.hallway[pos] = Some(amphipod);
+                let steps = depth + 1 + entrance.abs_diff(pos);
+                moves.push((next, steps * amphipod.energy()));
+            }
+        }
+        moves
+    }
+
+    /// Least total energy required to organize all amphipods into their rooms.
+    fn lowest_energy(&self) -> Option<usize> {
+        let mut lowest: HashMap<Burrow, usize> = HashMap::new();
+        let mut queue: BTreeSet<Candidate> = BTreeSet::new();
+        lowest.insert(self.clone(), 0);
+        queue.insert(Candidate {
+            energy: 0,
+            burrow: self.clone(),
+        });
+
+        while let Some(Candidate { energy, burrow }) = queue.pop_first() {
+            if burrow.is_organized() {
+                return Some(energy);
+            }
+            // check if we already found a better alternative
+            if lowest.get(&burrow).is_some_and(|e| *e < energy) {
+                continue;
+            }
+
+            for (next, required) in burrow.moves() {
+                let energy = energy + required;
+                if lowest.get(&next).is_none_or(|e| energy < *e) {
+                    lowest.insert(next.clone(), energy);
+                    queue.insert(Candidate {
+                        energy,
+                        burrow: next,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() -> Result<()> {
+        let content = read_to_string(PathBuf::from("debug.txt"))?;
+        let burrow = Burrow::read(&content)?;
+
+        assert_eq!(burrow.depth(), 2);
+        assert_eq!(burrow.to_string(), content);
+        assert_eq!(burrow.lowest_energy(), Some(12521));
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> Result<()> {
+        let burrow = Burrow::read(&read_to_string(PathBuf::from("debug.txt"))?)?.unfold();
+
+        assert_eq!(
+            burrow.to_string(),
+            "#############\n\
+             #...........#\n\
+             ###B#C#B#D###\n  \
+             #D#C#B#A#\n  \
+             #D#B#A#C#\n  \
+             #A#D#C#A#\n  \
+             #########\n"
+        );
+        assert_eq!(burrow.lowest_energy(), Some(44169));
+        Ok(())
+    }
+}
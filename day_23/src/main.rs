@@ -0,0 +1,6 @@
+use anyhow::Result;
+use day_23::Day23;
+
+fn main() -> Result<()> {
+    aoc_common::main::<Day23>()
+}
@@ -0,0 +1,423 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{parse_all_with, signed, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, not_line_ending, one_of,
+        space0, space1,
+    },
+    combinator::{map, map_res, value, verify},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Input = Vec<Instruction>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        parse_all_with(program, i)
+    }
+
+    fn part1(program: &Self::Input) -> Result<u64> {
+        let digits = find_model_number(&analyse(program)?, true)?;
+        validate(program, &digits)
+    }
+
+    fn part2(program: &Self::Input) -> Result<u64> {
+        let digits = find_model_number(&analyse(program)?, false)?;
+        validate(program, &digits)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Register(Register),
+    Literal(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+impl Parseable for Register {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        alt((
+            value(Register::W, char('w')),
+            value(Register::X, char('x')),
+            value(Register::Y, char('y')),
+            value(Register::Z, char('z')),
+        ))(i)
+    }
+}
+
+impl Parseable for Operand {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        alt((
+            map(Register::parse, Operand::Register),
+            map(signed, Operand::Literal),
+        ))(i)
+    }
+}
+
+impl Parseable for Instruction {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let binary = |name, op: fn(Register, Operand) -> Instruction| {
+            map(
+                preceded(
+                    pair(tag(name), space1),
+                    separated_pair(Register::parse, space1, Operand::parse),
+                ),
+                move |(reg, operand)| op(reg, operand),
+            )
+        };
+        alt((
+            map(
+                preceded(pair(tag("inp"), space1), Register::parse),
+                Self::Inp,
+            ),
+            binary("add", Self::Add),
+            binary("mul", Self::Mul),
+            binary("div", Self::Div),
+            binary("mod", Self::Mod),
+            binary("eql", Self::Eql),
+        ))(i)
+    }
+}
+
+fn program(i: &str) -> IResult<&str, Vec<Instruction>> {
+    separated_list1(line_ending, Instruction::parse)(i)
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Register::W => 'w',
+            Register::X => 'x',
+            Register::Y => 'y',
+            Register::Z => 'z',
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Register(reg) => write!(f, "{}", reg),
+            Operand::Literal(num) => write!(f, "{}", num),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Inp(reg) => write!(f, "inp {}", reg),
+            Instruction::Add(reg, op) => write!(f, "add {} {}", reg, op),
+            Instruction::Mul(reg, op) => write!(f, "mul {} {}", reg, op),
+            Instruction::Div(reg, op) => write!(f, "div {} {}", reg, op),
+            Instruction::Mod(reg, op) => write!(f, "mod {} {}", reg, op),
+            Instruction::Eql(reg, op) => write!(f, "eql {} {}", reg, op),
+        }
+    }
+}
+
+/// Arithmetic logic unit with four registers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Alu {
+    registers: [i64; 4],
+}
+
+impl Alu {
+    fn get(&self, reg: Register) -> i64 {
+        self.registers[reg as usize]
+    }
+
+    fn eval(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(reg) => self.get(reg),
+            Operand::Literal(num) => num,
+        }
+    }
+
+    /// Run `program` to completion, reading `inp` values from `input`.
+    fn run(&mut self, program: &[Instruction], input: &[i64]) -> Result<()> {
+        let mut input = input.iter();
+        for (idx, instruction) in program.iter().enumerate() {
+            let (reg, value) = match *instruction {
+                Instruction::Inp(reg) => (
+                    reg,
+                    Some(
+                        *input
+                            .next()
+                            .with_context(|| format!("Input exhausted at instruction {}", idx))?,
+                    ),
+                ),
+                Instruction::Add(reg, op) => (reg, self.get(reg).checked_add(self.eval(op))),
+                Instruction::Mul(reg, op) => (reg, self.get(reg).checked_mul(self.eval(op))),
+                Instruction::Div(reg, op) => {
+                    let divisor = self.eval(op);
+                    if divisor == 0 {
+                        bail!("Division by zero at instruction {}: {}", idx, instruction);
+                    }
+                    (reg, self.get(reg).checked_div(divisor))
+                }
+                Instruction::Mod(reg, op) => {
+                    let (a, b) = (self.get(reg), self.eval(op));
+                    if a < 0 || b <= 0 {
+                        bail!("Invalid modulo at instruction {}: {}", idx, instruction);
+                    }
+                    (reg, Some(a % b))
+                }
+                Instruction::Eql(reg, op) => (reg, Some((self.get(reg) == self.eval(op)) as i64)),
+            };
+            self.registers[reg as usize] = value
+                .with_context(|| format!("Overflow at instruction {}: {}", idx, instruction))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parameters of one of the 14 nearly identical blocks (one per digit) that make up MONAD.
+///
+/// Each block treats `z` as a stack of base-26 digits: blocks with `pop` remove the top entry
+/// and only avoid pushing a new one if the current digit equals that entry plus `check`.
+/// Blocks without `pop` always push the current digit plus `offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Block {
+    pop: bool,
+    check: i64,
+    offset: i64,
+}
+
+const BLOCK_LENGTH: usize = 18;
+
+impl Block {
+    fn instructions(&self) -> Vec<Instruction> {
+        use Instruction::*;
+        use Operand::Literal;
+        use Register::*;
+        let reg = Operand::Register;
+        vec![
+            Inp(W),
+            Mul(X, Literal(0)),
+            Add(X, reg(Z)),
+            Mod(X, Literal(26)),
+            Div(Z, Literal(if self.pop { 26 } else { 1 })),
+            Add(X, Literal(self.check)),
+            Eql(X, reg(W)),
+            Eql(X, Literal(0)),
+            Mul(Y, Literal(0)),
+            Add(Y, Literal(25)),
+            Mul(Y, reg(X)),
+            Add(Y, Literal(1)),
+            Mul(Z, reg(Y)),
+            Mul(Y, Literal(0)),
+            Add(Y, reg(W)),
+            Add(Y, Literal(self.offset)),
+            Mul(Y, reg(X)),
+            Add(Z, reg(Y)),
+        ]
+    }
+}
+
+/// Extract the block parameters from a MONAD program.
+fn analyse(program: &[Instruction]) -> Result<Vec<Block>> {
+    if !program.len().is_multiple_of(BLOCK_LENGTH) {
+        bail!(
+            "Program length {} is not a multiple of {}",
+            program.len(),
+            BLOCK_LENGTH
+        );
+    }
+
+    let literal = |instruction: Instruction| match instruction {
+        Instruction::Div(_, Operand::Literal(num)) | Instruction::Add(_, Operand::Literal(num)) => {
+            Some(num)
+        }
+        _ => None,
+    };
+    program
+        .chunks(BLOCK_LENGTH)
+        .enumerate()
+        .map(|(idx, chunk)| {
+            let block = Block {
+                pop: literal(chunk[4]) == Some(26),
+                check: literal(chunk[5]).unwrap_or_default(),
+                offset: literal(chunk[15]).unwrap_or_default(),
+            };
+            if block.instructions() != chunk {
+                bail!("Block {} does not follow the expected structure", idx);
+            }
+            Ok(block)
+        })
+        .collect()
+}
+
+/// Digits of the largest (or smallest) model number accepted by the analysed program.
+fn find_model_number(blocks: &[Block], largest: bool) -> Result<Vec<i64>> {
+    let mut digits = vec![0; blocks.len()];
+    let mut stack = Vec::new();
+
+    for (idx, block) in blocks.iter().enumerate() {
+        if !block.pop {
+            // x % 26 is in 0..=25, adding any of these checks can give a digit 1..=9
+            if (-24..=9).contains(&block.check) {
+                bail!("Block {} can avoid pushing, which is not supported", idx);
+            }
+            stack.push((idx, block.offset));
+            continue;
+        }
+        let (pushed, offset) = stack
+            .pop()
+            .with_context(|| format!("Block {} pops from an empty stack", idx))?;
+
+        // digits[idx] == digits[pushed] + diff
+        let diff = offset + block.check;
+        if diff.abs() > 8 {
+            bail!("Digits {} and {} cannot be matched", pushed, idx);
+        }
+        digits[pushed] = if largest {
+            9.min(9 - diff)
+        } else {
+            1.max(1 - diff)
+        };
+        digits[idx] = digits[pushed] + diff;
+    }
+    if !stack.is_empty() {
+        bail!("{} digits are never checked", stack.len());
+    }
+    Ok(digits)
+}
+
+/// Check the model number with the interpreter and convert it into a number.
+fn validate(program: &[Instruction], digits: &[i64]) -> Result<u64> {
+    let mut alu = Alu::default();
+    alu.run(program, digits)?;
+    if alu.get(Register::Z) != 0 {
+        bail!("Model number {:?} is rejected", digits);
+    }
+    Ok(digits.iter().fold(0, |num, d| num * 10 + *d as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// MONAD-like program with nested push/pop pairs.
+    fn monad() -> Vec<Instruction> {
+        let push = |offset| Block {
+            pop: false,
+            check: 12,
+            offset,
+        };
+        let pop = |check| Block {
+            pop: true,
+            check,
+            offset: 7,
+        };
+        [
+            push(4),
+            push(11),
+            pop(-13),
+            push(0),
+            push(6),
+            pop(-1),
+            pop(-7),
+            push(3),
+            pop(-3),
+            push(12),
+            push(1),
+            pop(0),
+            pop(-8),
+            pop(-5),
+        ]
+        .iter()
+        .flat_map(|b| b.instructions())
+        .collect()
+    }
+
+    #[test]
+    fn test_interpreter() -> Result<()> {
+        let program = Day24::parse(&read_to_string(PathBuf::from("debug.txt"))?)?;
+        assert_eq!(program.len(), 11);
+
+        let mut alu = Alu::default();
+        alu.run(&program, &[13])?;
+        assert_eq!(alu.registers, [1, 1, 0, 1]);
+
+        let negate = Day24::parse("inp x\nmul x -1\n")?;
+        let mut alu = Alu::default();
+        alu.run(&negate, &[7])?;
+        assert_eq!(alu.get(Register::X), -7);
+        assert!(alu.run(&negate, &[]).is_err());
+
+        for program in [
+            "inp x\nmul x 4611686018427387904\n",
+            "inp x\nadd x 9223372036854775807\n",
+            "inp x\nmul x -4611686018427387904\ndiv x -1\n",
+        ] {
+            let err = Alu::default()
+                .run(&Day24::parse(program)?, &[2])
+                .unwrap_err();
+            assert!(err.to_string().contains("Overflow"), "{}", err);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_part1() -> Result<()> {
+        let program = Day24::parse(&read_to_string(PathBuf::from("debug-monad.txt"))?)?;
+        assert_eq!(program, monad());
+
+        assert_eq!(Day24::part1(&program)?, 99794929958998);
+        assert!(validate(&program, &[9, 9, 7, 9, 4, 9, 2, 9, 9, 5, 8, 9, 9, 9]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> Result<()> {
+        let program = Day24::parse(&read_to_string(PathBuf::from("debug-monad.txt"))?)?;
+
+        assert_eq!(Day24::part2(&program)?, 23181611111251);
+        assert!(analyse(&program[1..]).is_err());
+
+        // a push block whose check can match a digit might not push
+        let mut blocks = analyse(&program)?;
+        blocks[0].check = -3;
+        let err = find_model_number(&blocks, false).unwrap_err();
+        assert!(err.to_string().contains("avoid pushing"), "{}", err);
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+#![allow(unused_imports)]
+use anyhow::{bail, Context, Error, Result};
+use aoc_common::{grid, parse_all, Parseable, Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_while1},
+    character::complete::{
+        alpha1, anychar, char, digit1, line_ending, multispace1, none_of, not_line_ending, one_of,
+        space0, space1,
+    },
+    combinator::{map, map_res, value, verify},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Err::{Failure, Incomplete},
+    ErrorConvert, Finish, IResult,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+
+    type Input = Grid;
+    type Answer1 = usize;
+    type Answer2 = &'static str;
+
+    fn parse(i: &str) -> Result<Self::Input> {
+        Grid::read(i)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<usize> {
+        Ok(grid.clone().settle())
+    }
+
+    fn part2(_grid: &Self::Input) -> Result<&'static str> {
+        // the last day only has a single puzzle
+        Ok("-")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+fn cell(i: &str) -> IResult<&str, Cell> {
+    alt((
+        value(Cell::Empty, char('.')),
+        value(Cell::East, char('>')),
+        value(Cell::South, char('v')),
+    ))(i)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    grid: Vec<Vec<Cell>>,
+    size_x: usize,
+    size_y: usize,
+}
+
+impl Parseable for Grid {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        let (i, grid) = grid(cell)(i)?;
+
+        let size_y = grid.len();
+        let size_x = grid[0].len();
+
+        Ok((
+            i,
+            Self {
+                grid,
+                size_x,
+                size_y,
+            },
+        ))
+    }
+}
+
+impl Grid {
+    fn read(i: &str) -> Result<Self> {
+        parse_all(i)
+    }
+
+    /// Move both herds once, returning whether any sea cucumber moved.
+    fn step(&mut self) -> bool {
+        let moved_east = self.move_herd(Cell::East, 1, 0);
+        let moved_south = self.move_herd(Cell::South, 0, 1);
+        moved_east || moved_south
+    }
+
+    /// Simultaneously move all sea cucumbers of `herd` by (dx, dy), wrapping around the edges.
+    fn move_herd(&mut self, herd: Cell, dx: usize, dy: usize) -> bool {
+        let mut moves = Vec::new();
+        for y in 0..self.size_y {
+            for x in 0..self.size_x {
+                let (to_x, to_y) = ((x + dx) % self.size_x, (y + dy) % self.size_y);
+                if self.grid[y][x] == herd && self.grid[to_y][to_x] == Cell::Empty {
+                    moves.push(((x, y), (to_x, to_y)));
+                }
+            }
+        }
+        for ((x, y), (to_x, to_y)) in moves.iter() {
+            self.grid[*y][*x] = Cell::Empty;
+            self.grid[*to_y][*to_x] = herd;
+        }
+        !moves.is_empty()
+    }
+
+    /// Step until nothing moves anymore, returning the number of the first step without moves.
+    fn settle(&mut self) -> usize {
+        let mut round = 1;
+        while self.step() {
+            round += 1;
+        }
+        round
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in self.grid.iter() {
+            for cell in line.iter() {
+                let c = match cell {
+                    Cell::Empty => '.',
+                    Cell::East => '>',
+                    Cell::South => 'v',
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step() -> Result<()> {
+        let mut grid = Grid::read("...>>>>>...")?;
+
+        assert!(grid.step());
+        assert_eq!(grid.to_string(), "...>>>>.>..\n");
+        assert!(grid.step());
+        assert_eq!(grid.to_string(), "...>>>.>.>.\n");
+
+        let mut grid = Grid::read("..........\n.>v....v..\n.......>..\n..........\n")?;
+        grid.step();
+        assert_eq!(
+            grid.to_string(),
+            "..........\n.>........\n..v....v>.\n..........\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_part1() -> Result<()> {
+        let mut grid = Grid::read(&read_to_string(PathBuf::from("debug.txt"))?)?;
+
+        assert_eq!(grid.size_x, 10);
+        assert_eq!(grid.size_y, 9);
+
+        assert_eq!(grid.settle(), 58);
+        assert_eq!(
+            grid.to_string(),
+            "..>>v>vv..\n..v.>>vv..\n..>>v>>vv.\n..>>>>>vv.\nv......>vv\nv>v....>>v\n\
+             vvv.....>>\n>vv......>\n.>v.vv.v..\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,6 @@
+use anyhow::Result;
+use day_25::Day25;
+
+fn main() -> Result<()> {
+    aoc_common::main::<Day25>()
+}